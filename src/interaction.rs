@@ -0,0 +1,56 @@
+use math::point::{Point2f, Point3f};
+use math::normal::Normal3f;
+use math::vector::Vector3f;
+use math::ray::{self, Ray};
+use math::scalar::FloatScalar;
+
+// The hit record produced when a ray intersects a surface. `wo` points back towards the origin
+// of the incoming ray, and `time` is carried over from it so spawned rays stay in sync.
+#[derive(PartialEq, Copy, Clone)]
+pub struct SurfaceInteraction {
+    pub p: Point3f,
+    pub n: Normal3f,
+    pub wo: Vector3f,
+    pub uv: Point2f,
+    pub t: FloatScalar,
+    pub time: FloatScalar,
+}
+
+impl SurfaceInteraction {
+    pub fn new(p: Point3f, n: Normal3f, wo: Vector3f, uv: Point2f, t: FloatScalar,
+            time: FloatScalar) -> SurfaceInteraction {
+        SurfaceInteraction {
+            p: p,
+            n: n,
+            wo: wo,
+            uv: uv,
+            t: t,
+            time: time,
+        }
+    }
+
+    pub fn spawn_ray(&self, d: Vector3f) -> Ray {
+        ray::spawn_ray(self.p, self.n, d).with_time(self.time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::common::ApproxEq;
+    use math::vector::{reflect, vec3};
+
+    #[test]
+    fn spawned_reflection_ray() {
+        let incident = vec3(1.0, 0.0, -1.0);
+        let n = Normal3f::new(0.0, 0.0, 1.0);
+        let si = SurfaceInteraction::new(Point3f::new(2.0, 3.0, 0.0), n, -incident,
+                                         Point2f::new(0.5, 0.5), 4.0, 0.75);
+        let ray = si.spawn_ray(reflect(incident, Vector3f::from(n)));
+        assert_eq!(ray.time, 0.75);
+        assert!(ray.direction.approx_eq(vec3(1.0, 0.0, 1.0), 1e-6));
+        // The origin is pushed off the surface towards the side the ray leaves from.
+        assert!(ray.origin.z > 0.0);
+        assert!(Point3f::new(ray.origin.x, ray.origin.y, 0.0) == si.p);
+    }
+}
@@ -1,6 +1,13 @@
 extern crate num;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
 pub mod math;
+pub mod shapes;
+pub mod interaction;
 
 
 fn main() {
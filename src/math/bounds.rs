@@ -0,0 +1,422 @@
+use math::point::{Point2, Point3};
+use math::vector::{Vector2, Vector3, Vector3f};
+use math::ray::Ray;
+use math::common::*;
+use math::scalar::*;
+use std::mem;
+
+#[derive(PartialEq, Copy, Clone)]
+pub struct Bounds2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+#[derive(PartialEq, Copy, Clone)]
+pub struct Bounds3<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+//
+// Bounds2
+//
+impl <T: BaseNum> Bounds2<T> {
+    pub fn new(p1: Point2<T>, p2: Point2<T>) -> Bounds2<T> {
+        Bounds2 {
+            min: component_wise_min(p1, p2),
+            max: component_wise_max(p1, p2),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min.x <= self.max.x && self.min.y <= self.max.y
+    }
+
+    pub fn diagonal(&self) -> Vector2<T> {
+        self.max - self.min
+    }
+
+    pub fn area(&self) -> T {
+        let d = self.diagonal();
+        d.x * d.y
+    }
+}
+
+impl Bounds2<IntScalar> {
+    pub fn iter(&self) -> Points2i {
+        Points2i {
+            bounds: *self,
+            current: self.min,
+        }
+    }
+}
+
+impl <T: BaseNum> From<Point2<T>> for Bounds2<T> {
+    fn from(p: Point2<T>) -> Bounds2<T> {
+        Bounds2 {
+            min: p,
+            max: p,
+        }
+    }
+}
+
+impl IntoIterator for Bounds2<IntScalar> {
+    type Item = Point2<IntScalar>;
+    type IntoIter = Points2i;
+
+    fn into_iter(self) -> Points2i {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &Bounds2<IntScalar> {
+    type Item = Point2<IntScalar>;
+    type IntoIter = Points2i;
+
+    fn into_iter(self) -> Points2i {
+        self.iter()
+    }
+}
+
+// Walks the points of a Bounds2i in row-major order, with `min` inclusive and `max` exclusive.
+pub struct Points2i {
+    bounds: Bounds2<IntScalar>,
+    current: Point2<IntScalar>,
+}
+
+impl Iterator for Points2i {
+    type Item = Point2<IntScalar>;
+
+    fn next(&mut self) -> Option<Point2<IntScalar>> {
+        if self.bounds.min.x >= self.bounds.max.x || self.current.y >= self.bounds.max.y {
+            return None;
+        }
+
+        let p = self.current;
+        self.current.x += 1;
+        if self.current.x >= self.bounds.max.x {
+            self.current.x = self.bounds.min.x;
+            self.current.y += 1;
+        }
+        Some(p)
+    }
+}
+
+//
+// Bounds3
+//
+impl <T: BaseNum> Bounds3<T> {
+    pub fn new(p1: Point3<T>, p2: Point3<T>) -> Bounds3<T> {
+        Bounds3 {
+            min: component_wise_min(p1, p2),
+            max: component_wise_max(p1, p2),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn expand(&self, delta: T) -> Bounds3<T> {
+        let d = Vector3::from(delta);
+        Bounds3 {
+            min: self.min - d,
+            max: self.max + d,
+        }
+    }
+
+    // Bits 0, 1 and 2 of `i` pick `max` over `min` for x, y and z; higher bits are ignored.
+    pub fn corner(&self, i: usize) -> Point3<T> {
+        Point3::new(if i & 1 == 0 { self.min.x } else { self.max.x },
+                    if i & 2 == 0 { self.min.y } else { self.max.y },
+                    if i & 4 == 0 { self.min.z } else { self.max.z })
+    }
+
+    pub fn corners(&self) -> [Point3<T>; 8] {
+        [self.corner(0), self.corner(1), self.corner(2), self.corner(3),
+         self.corner(4), self.corner(5), self.corner(6), self.corner(7)]
+    }
+
+    pub fn iter_corners(&self) -> Corners<T> {
+        Corners {
+            bounds: *self,
+            index: 0,
+        }
+    }
+
+    pub fn diagonal(&self) -> Vector3<T> {
+        self.max - self.min
+    }
+
+    pub fn surface_area(&self) -> T {
+        let d = self.diagonal();
+        (T::one() + T::one()) * (d.x * d.y + d.x * d.z + d.y * d.z)
+    }
+
+    pub fn volume(&self) -> T {
+        let d = self.diagonal();
+        d.x * d.y * d.z
+    }
+
+    pub fn maximum_extent(&self) -> Dimension3 {
+        self.diagonal().max_dimension()
+    }
+}
+
+impl <T: BaseFloat> Bounds3<T> {
+    pub fn empty() -> Bounds3<T> {
+        Bounds3 {
+            min: Point3::from(T::infinity()),
+            max: Point3::from(T::neg_infinity()),
+        }
+    }
+
+    // Points with a NaN or infinite coordinate are skipped; if none of the points are finite the
+    // returned box is empty and `is_valid()` is false.
+    pub fn from_points(points: &[Point3<T>]) -> Bounds3<T> {
+        points.iter()
+            .filter(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite())
+            .fold(Bounds3::empty(), |b, &p| Bounds3 {
+                min: component_wise_min(b.min, p),
+                max: component_wise_max(b.max, p),
+            })
+    }
+
+    pub fn centroid(&self) -> Point3<T> {
+        self.min.lerp(self.max, T::from(0.5).unwrap())
+    }
+
+    pub fn bounding_sphere(&self) -> (Point3<T>, T) {
+        let center = self.centroid();
+        let radius = if self.is_valid() { center.distance(self.max) } else { T::zero() };
+        (center, radius)
+    }
+
+    pub fn lerp(&self, t: Point3<T>) -> Point3<T> {
+        Point3::new(lerp(t.x, self.min.x, self.max.x),
+                    lerp(t.y, self.min.y, self.max.y),
+                    lerp(t.z, self.min.z, self.max.z))
+    }
+
+    // The position of `p` relative to the box, where `min` is at 0 and `max` is at 1 along each
+    // axis. Axes where the box has no width always map to 0.
+    pub fn offset(&self, p: Point3<T>) -> Vector3<T> {
+        let mut o = p - self.min;
+        for i in 0..3 {
+            o[i] = if self.max[i] > self.min[i] { o[i] / (self.max[i] - self.min[i]) } else { T::zero() };
+        }
+        o
+    }
+}
+
+impl Bounds3<FloatScalar> {
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<(FloatScalar, FloatScalar)> {
+        let mut t0 = 0.0;
+        let mut t1 = ray.tmax;
+
+        for i in 0..3 {
+            let inv_dir = 1.0 / ray.direction[i];
+            let mut t_near = (self.min[i] - ray.origin[i]) * inv_dir;
+            let mut t_far = (self.max[i] - ray.origin[i]) * inv_dir;
+            if t_near > t_far {
+                mem::swap(&mut t_near, &mut t_far);
+            }
+
+            t0 = partial_max(t_near, t0);
+            t1 = partial_min(t_far, t1);
+            if t0 > t1 {
+                return None;
+            }
+        }
+
+        Some((t0, t1))
+    }
+
+    pub fn intersect_ray_fast(&self, ray: &Ray, inv_dir: Vector3f, dir_is_neg: [bool; 3]) -> bool {
+        let near = |i: usize| if dir_is_neg[i] { self.max[i] } else { self.min[i] };
+        let far = |i: usize| if dir_is_neg[i] { self.min[i] } else { self.max[i] };
+
+        let mut t_min = (near(0) - ray.origin.x) * inv_dir.x;
+        let mut t_max = (far(0) - ray.origin.x) * inv_dir.x;
+        let ty_min = (near(1) - ray.origin.y) * inv_dir.y;
+        let ty_max = (far(1) - ray.origin.y) * inv_dir.y;
+
+        if t_min > ty_max || ty_min > t_max {
+            return false;
+        }
+        t_min = partial_max(t_min, ty_min);
+        t_max = partial_min(t_max, ty_max);
+
+        let tz_min = (near(2) - ray.origin.z) * inv_dir.z;
+        let tz_max = (far(2) - ray.origin.z) * inv_dir.z;
+
+        if t_min > tz_max || tz_min > t_max {
+            return false;
+        }
+        t_min = partial_max(t_min, tz_min);
+        t_max = partial_min(t_max, tz_max);
+
+        t_min < ray.tmax && t_max > 0.0
+    }
+}
+
+impl <T: BaseNum> From<Point3<T>> for Bounds3<T> {
+    fn from(p: Point3<T>) -> Bounds3<T> {
+        Bounds3 {
+            min: p,
+            max: p,
+        }
+    }
+}
+
+// The default box is `empty()`, which contains no points.
+impl <T: BaseFloat> Default for Bounds3<T> {
+    fn default() -> Bounds3<T> {
+        Bounds3::empty()
+    }
+}
+
+pub struct Corners<T> {
+    bounds: Bounds3<T>,
+    index: usize,
+}
+
+impl <T: BaseNum> Iterator for Corners<T> {
+    type Item = Point3<T>;
+
+    fn next(&mut self) -> Option<Point3<T>> {
+        if self.index > 7 {
+            None
+        } else {
+            self.index += 1;
+            Some(self.bounds.corner(self.index - 1))
+        }
+    }
+}
+
+pub type Bounds2i = Bounds2<IntScalar>;
+pub type Bounds2f = Bounds2<FloatScalar>;
+pub type Bounds3i = Bounds3<IntScalar>;
+pub type Bounds3f = Bounds3<FloatScalar>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::Float;
+    use num::Zero;
+
+    fn unit_cube() -> Bounds3f {
+        Bounds3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn unit_cube_measurements() {
+        let b = unit_cube();
+        assert_eq!(b.diagonal(), Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(b.surface_area(), 6.0);
+        assert_eq!(b.volume(), 1.0);
+        assert!(b.centroid() == Point3::new(0.5, 0.5, 0.5));
+
+        let stretched = Bounds3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 3.0, 2.0));
+        assert_eq!(stretched.maximum_extent(), Dimension3::Y);
+    }
+
+    #[test]
+    fn corner_uses_low_bits() {
+        let b = unit_cube();
+        assert!(b.corner(0) == b.min);
+        assert!(b.corner(7) == b.max);
+        assert!(b.corner(5) == Point3::new(1.0, 0.0, 1.0));
+        assert!(b.corner(8 + 5) == b.corner(5));
+    }
+
+    #[test]
+    fn from_points_skips_nan() {
+        let b = Bounds3::from_points(&[Point3::new(1.0, 2.0, 3.0),
+                                       Point3::new(FloatScalar::nan(), 0.0, 0.0),
+                                       Point3::new(-1.0, 5.0, 0.0)]);
+        assert!(b.is_valid());
+        assert!(b.min == Point3::new(-1.0, 2.0, 0.0));
+        assert!(b.max == Point3::new(1.0, 5.0, 3.0));
+
+        assert!(!Bounds3f::from_points(&[Point3::new(FloatScalar::nan(), 0.0, 0.0)]).is_valid());
+    }
+
+    #[test]
+    fn corners_are_distinct_and_on_the_surface() {
+        let b = Bounds3::new(Point3::new(-1.0, 0.0, 2.0), Point3::new(3.0, 0.5, 4.0));
+        let corners = b.corners();
+        for (i, &c) in corners.iter().enumerate() {
+            assert!(corners[i + 1..].iter().all(|&other| other != c));
+            assert!((0..3).all(|axis| c[axis] == b.min[axis] || c[axis] == b.max[axis]));
+        }
+        assert!(b.iter_corners().eq(corners.iter().cloned()));
+    }
+
+    fn intersects_fast(b: &Bounds3f, ray: &Ray) -> bool {
+        let inv_dir = Vector3f::from(1.0) / ray.direction;
+        b.intersect_ray_fast(ray, inv_dir, ray.direction.cmplt(Vector3f::zero()))
+    }
+
+    #[test]
+    fn ray_intersection() {
+        let b = unit_cube();
+
+        let hit = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(b.intersect_ray(&hit), Some((1.0, 2.0)));
+        assert!(intersects_fast(&b, &hit));
+        assert!(intersects_fast(&b, &Ray::new(Point3::new(2.0, 0.5, 0.5), Vector3::new(-1.0, 0.0, 0.0))));
+
+        let beside = Ray::new(Point3::new(-1.0, 2.0, 0.5), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(b.intersect_ray(&beside), None);
+        assert!(!intersects_fast(&b, &beside));
+
+        let short = hit.with_tmax(0.5);
+        assert_eq!(b.intersect_ray(&short), None);
+        assert!(!intersects_fast(&b, &short));
+
+        let inside = Ray::new(Point3::new(0.5, 0.5, 0.5), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(b.intersect_ray(&inside), Some((0.0, 0.5)));
+        assert!(intersects_fast(&b, &inside));
+    }
+
+    #[test]
+    fn points2i_row_major() {
+        let b = Bounds2::new(Point2::new(1, 4), Point2::new(4, 6));
+        let points: Vec<(IntScalar, IntScalar)> = b.iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(points, vec![(1, 4), (2, 4), (3, 4), (1, 5), (2, 5), (3, 5)]);
+        assert_eq!((&b).into_iter().count(), 6);
+
+        assert_eq!(Bounds2::new(Point2::new(1, 4), Point2::new(1, 6)).iter().count(), 0);
+        assert_eq!(Bounds2::new(Point2::new(1, 4), Point2::new(4, 4)).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn bounding_sphere_lerp_and_offset() {
+        let b = unit_cube();
+        let (center, radius) = b.bounding_sphere();
+        assert!(center == Point3::new(0.5, 0.5, 0.5));
+        assert!(radius.approx_eq(FloatScalar::sqrt(3.0) / 2.0, 1e-6));
+        assert_eq!(b.offset(center), Vector3::new(0.5, 0.5, 0.5));
+
+        let b = Bounds3::new(Point3::new(-2.0, 0.0, 1.0), Point3::new(2.0, 4.0, 1.0));
+        assert!(b.lerp(Point3::new(0.25, 0.5, 1.0)) == Point3::new(-1.0, 2.0, 1.0));
+        assert_eq!(b.offset(Point3::new(-1.0, 2.0, 1.0)), Vector3::new(0.25, 0.5, 0.0));
+        assert_eq!(Bounds3f::empty().bounding_sphere().1, 0.0);
+    }
+
+    #[test]
+    fn expand_and_is_empty() {
+        let b = unit_cube().expand(0.1);
+        assert!(b.min.approx_eq(Point3::new(-0.1, -0.1, -0.1), 1e-6));
+        assert!(b.max.approx_eq(Point3::new(1.1, 1.1, 1.1), 1e-6));
+        assert!(!b.is_empty());
+
+        assert!(Bounds3f::default().is_empty());
+        assert!(!Bounds3::from(Point3::new(1.0, 2.0, 3.0)).is_empty());
+    }
+}
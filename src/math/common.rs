@@ -1,18 +1,58 @@
 use num::{Zero, One, Signed, Float};
 use math::scalar::{BaseNum, BaseFloat};
 use std::ops::{Add, Sub, Mul, Div, Index, Neg};
+use std::convert::{From, TryFrom};
+use std::fmt;
 
-#[derive(Debug)]
+// The discriminants match the `Index<usize>` order of the components.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum Dimension2 {
-    X = 1,
-    Y = 2,
+    X = 0,
+    Y = 1,
 }
 
-#[derive(Debug)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum Dimension3 {
-    X = 1,
-    Y = 2,
-    Z = 3,
+    X = 0,
+    Y = 1,
+    Z = 2,
+}
+
+impl From<Dimension2> for usize {
+    fn from(d: Dimension2) -> usize {
+        d as usize
+    }
+}
+
+impl TryFrom<usize> for Dimension2 {
+    type Error = usize;
+
+    fn try_from(i: usize) -> Result<Dimension2, usize> {
+        match i {
+            0 => Ok(Dimension2::X),
+            1 => Ok(Dimension2::Y),
+            _ => Err(i),
+        }
+    }
+}
+
+impl From<Dimension3> for usize {
+    fn from(d: Dimension3) -> usize {
+        d as usize
+    }
+}
+
+impl TryFrom<usize> for Dimension3 {
+    type Error = usize;
+
+    fn try_from(i: usize) -> Result<Dimension3, usize> {
+        match i {
+            0 => Ok(Dimension3::X),
+            1 => Ok(Dimension3::Y),
+            2 => Ok(Dimension3::Z),
+            _ => Err(i),
+        }
+    }
 }
 
 pub trait ComponentWise where 
@@ -76,6 +116,32 @@ pub trait InnerProductSpace: InnerProduct where
     fn normalize(self) -> Self {
         self * (Self::Scalar::one() / self.magnitude())
     }
+
+    // Equivalent to `normalize`; std has no approximate reciprocal square root to build a faster
+    // path on, so this exists for call sites that want to opt into one if it becomes available.
+    fn normalize_fast(self) -> Self {
+        self.normalize()
+    }
+
+    fn is_normalized(self, epsilon: Self::Scalar) -> bool {
+        Float::abs(self.magnitude_squared() - Self::Scalar::one()) <= epsilon
+    }
+
+    fn try_normalize(self) -> Option<Self> {
+        if self.magnitude_squared() < Self::Scalar::min_positive_value() {
+            None
+        } else {
+            Some(self.normalize())
+        }
+    }
+
+    // Uses the half-angle form `2 * atan2(|a - b|, |a + b|)` on the normalized vectors, which
+    // stays accurate near 0 and π where `acos` of the dot product loses precision.
+    fn angle_between(self, other: Self) -> Self::Scalar {
+        let a = self.normalize();
+        let b = other.normalize();
+        (Self::Scalar::one() + Self::Scalar::one()) * (a - b).magnitude().atan2((a + b).magnitude())
+    }
 }
 
 pub trait MetricSpace<RHS = Self>: Copy + Clone {
@@ -98,6 +164,14 @@ pub trait LinearInterpolate: Copy + Clone where
     }
 }
 
+pub trait ApproxEq: Copy + Clone {
+    type Scalar: BaseFloat;
+
+    fn approx_eq(self, other: Self, epsilon: Self::Scalar) -> bool;
+
+    fn relative_eq(self, other: Self, epsilon: Self::Scalar) -> bool;
+}
+
 pub fn dot<T: InnerProduct, U: InnerProduct<T>>(v1: U, v2: T) -> U::Scalar {
     v1.dot(v2)
 }
@@ -111,6 +185,22 @@ pub fn cross<T: CrossProduct, U: CrossProduct<T>>(v1: U, v2: T) -> U::CrossOutpu
     v1.cross(v2)
 }
 
+pub fn magnitude<T: InnerProductSpace>(v: T) -> T::Scalar where T::Scalar: BaseFloat {
+    v.magnitude()
+}
+
+pub fn normalize<T: InnerProductSpace>(v: T) -> T where T::Scalar: BaseFloat {
+    v.normalize()
+}
+
+pub fn all(mask: &[bool]) -> bool {
+    mask.iter().all(|&b| b)
+}
+
+pub fn any(mask: &[bool]) -> bool {
+    mask.iter().any(|&b| b)
+}
+
 pub fn min_component<T: ComponentWise>(v: T) -> T::Scalar {
     v.min_component()
 }
@@ -135,10 +225,115 @@ pub fn component_wise_max<T: ComponentWise>(v1: T, v2: T) -> T {
     v1.max(v2)
 }
 
+// Bilinear interpolation between four corners, where `p10` is one step along `s` from `p00` and
+// `p01` is one step along `t`.
+pub fn bilerp<T: LinearInterpolate>(p00: T, p10: T, p01: T, p11: T, s: T::Scalar, t: T::Scalar) -> T {
+    p00.lerp(p10, s).lerp(p01.lerp(p11, s), t)
+}
+
 pub fn face_forward<T: InnerProduct, U: InnerProduct<T> + Neg<Output = U>>(v1: U, v2: T) -> U {
     if dot(v1, v2) < U::Scalar::zero() {
         -v1
     } else {
         v1
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn format_component<T: fmt::Display>(x: T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, x),
+        None => format!("{}", x),
+    }
+}
+
+pub(crate) fn format_component_exp<T: fmt::LowerExp>(x: T, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*e}", p, x),
+        None => format!("{:e}", x),
+    }
+}
+
+// Writes the components as `[x, y, z]`, formatting each one with `format`.
+pub(crate) fn fmt_components<T, F>(f: &mut fmt::Formatter, components: &[T], format: F) -> fmt::Result
+        where T: Copy, F: Fn(T, Option<usize>) -> String {
+    let formatted: Vec<String> = components.iter()
+        .map(|&c| format(c, f.precision()))
+        .collect();
+    write!(f, "[{}]", formatted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::vector::{Vector3f, vec3};
+    use math::normal::Normal3f;
+    use math::scalar::radians;
+
+    #[test]
+    fn free_magnitude_and_normalize() {
+        let v = vec3(3.0, 0.0, 4.0);
+        assert_eq!(magnitude(v), 5.0);
+        assert!(normalize(v).approx_eq(vec3(0.6, 0.0, 0.8), 1e-6));
+
+        let n = Normal3f::new(0.0, -2.0, 0.0);
+        assert_eq!(magnitude(n), 2.0);
+        assert!(normalize(n) == Normal3f::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn try_normalize() {
+        assert_eq!(Vector3f::zero().try_normalize(), None);
+        let v = vec3(1.0, -2.0, 2.0).try_normalize().unwrap();
+        assert!(v.magnitude().approx_eq(1.0, 1e-6));
+        assert!(v.approx_eq(vec3(1.0, -2.0, 2.0) / 3.0, 1e-6));
+    }
+
+    #[test]
+    fn angle_between() {
+        let x = Vector3f::unit_x();
+        assert!(x.angle_between(Vector3f::unit_y() * 2.0).approx_eq(radians(90.0), 1e-6));
+        assert_eq!(x.angle_between(x), 0.0);
+
+        // With f32, `acos` of the dot product would give exactly π here.
+        let nearly_opposite = vec3(-1.0, 1e-4, 0.0);
+        assert!(x.angle_between(nearly_opposite).approx_eq(radians(180.0) - 1e-4, 1e-6));
+    }
+
+    #[test]
+    fn is_normalized() {
+        let v = vec3(3.0, -1.0, 7.0);
+        assert!(!v.is_normalized(1e-5));
+        assert!(v.normalize().is_normalized(1e-5));
+        assert!(v.normalize_fast().is_normalized(1e-5));
+        assert_eq!(v.normalize_fast(), v.normalize());
+    }
+
+    #[test]
+    fn dimension_conversions() {
+        for &d in [Dimension3::X, Dimension3::Y, Dimension3::Z].iter() {
+            assert_eq!(Dimension3::try_from(usize::from(d)), Ok(d));
+        }
+        for &d in [Dimension2::X, Dimension2::Y].iter() {
+            assert_eq!(Dimension2::try_from(usize::from(d)), Ok(d));
+        }
+        assert_eq!(Dimension3::try_from(3), Err(3));
+        assert_eq!(Dimension2::try_from(2), Err(2));
+
+        let v = vec3(4.0, 5.0, 6.0);
+        assert_eq!(v[Dimension3::X], v[0usize]);
+        assert_eq!(v[Dimension3::Z], v[usize::from(Dimension3::Z)]);
+    }
+
+    #[test]
+    fn bilerp_corners_and_center() {
+        let (p00, p10, p01, p11) = (0.0, 1.0, 2.0, 5.0);
+        assert_eq!(bilerp(p00, p10, p01, p11, 0.0, 0.0), p00);
+        assert_eq!(bilerp(p00, p10, p01, p11, 1.0, 0.0), p10);
+        assert_eq!(bilerp(p00, p10, p01, p11, 0.0, 1.0), p01);
+        assert_eq!(bilerp(p00, p10, p01, p11, 1.0, 1.0), p11);
+        assert_eq!(bilerp(p00, p10, p01, p11, 0.5, 0.5), 2.0);
+
+        let v = bilerp(Vector3f::zero(), Vector3f::unit_x(), Vector3f::unit_y(), vec3(1.0, 1.0, 1.0), 0.5, 0.5);
+        assert_eq!(v, vec3(0.5, 0.5, 0.25));
+    }
+}
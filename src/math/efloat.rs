@@ -0,0 +1,163 @@
+use num::Float;
+use math::scalar::*;
+use std::convert::From;
+use std::ops::*;
+
+#[derive(Copy, Clone, Debug)]
+pub struct EFloat {
+    value: FloatScalar,
+    low: FloatScalar,
+    high: FloatScalar,
+}
+
+impl EFloat {
+    pub fn new(value: FloatScalar, err: FloatScalar) -> EFloat {
+        if err == 0.0 {
+            EFloat::from(value)
+        } else {
+            EFloat::from_interval(value, value - err, value + err)
+        }
+    }
+
+    // Each arithmetic result is rounded once, so the interval is pushed out by at most one
+    // rounding error in each direction to keep it conservative.
+    fn from_interval(value: FloatScalar, low: FloatScalar, high: FloatScalar) -> EFloat {
+        EFloat {
+            value: value,
+            low: low - low.abs() * gamma(1),
+            high: high + high.abs() * gamma(1),
+        }
+    }
+
+    pub fn value(&self) -> FloatScalar {
+        self.value
+    }
+
+    pub fn lower_bound(&self) -> FloatScalar {
+        self.low
+    }
+
+    pub fn upper_bound(&self) -> FloatScalar {
+        self.high
+    }
+
+    pub fn absolute_error(&self) -> FloatScalar {
+        partial_max((self.high - self.value).abs(), (self.value - self.low).abs())
+    }
+
+    pub fn sqrt(&self) -> EFloat {
+        EFloat::from_interval(self.value.sqrt(), self.low.sqrt(), self.high.sqrt())
+    }
+}
+
+impl From<FloatScalar> for EFloat {
+    fn from(value: FloatScalar) -> EFloat {
+        EFloat {
+            value: value,
+            low: value,
+            high: value,
+        }
+    }
+}
+
+impl Neg for EFloat {
+    type Output = EFloat;
+
+    fn neg(self) -> EFloat {
+        EFloat {
+            value: -self.value,
+            low: -self.high,
+            high: -self.low,
+        }
+    }
+}
+
+impl Add for EFloat {
+    type Output = EFloat;
+
+    fn add(self, other: EFloat) -> EFloat {
+        EFloat::from_interval(self.value + other.value, self.low + other.low, self.high + other.high)
+    }
+}
+
+impl Sub for EFloat {
+    type Output = EFloat;
+
+    fn sub(self, other: EFloat) -> EFloat {
+        EFloat::from_interval(self.value - other.value, self.low - other.high, self.high - other.low)
+    }
+}
+
+impl Mul for EFloat {
+    type Output = EFloat;
+
+    fn mul(self, other: EFloat) -> EFloat {
+        let products = [self.low * other.low, self.high * other.low,
+                        self.low * other.high, self.high * other.high];
+
+        EFloat::from_interval(self.value * other.value,
+            partial_min(partial_min(products[0], products[1]), partial_min(products[2], products[3])),
+            partial_max(partial_max(products[0], products[1]), partial_max(products[2], products[3])))
+    }
+}
+
+impl Div for EFloat {
+    type Output = EFloat;
+
+    fn div(self, other: EFloat) -> EFloat {
+        if other.low < 0.0 && other.high > 0.0 {
+            // The divisor's interval straddles zero, so the quotient could be anything.
+            EFloat {
+                value: self.value / other.value,
+                low: Float::neg_infinity(),
+                high: Float::infinity(),
+            }
+        } else {
+            let quotients = [self.low / other.low, self.high / other.low,
+                             self.low / other.high, self.high / other.high];
+
+            EFloat::from_interval(self.value / other.value,
+                partial_min(partial_min(quotients[0], quotients[1]), partial_min(quotients[2], quotients[3])),
+                partial_max(partial_max(quotients[0], quotients[1]), partial_max(quotients[2], quotients[3])))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::ToPrimitive;
+
+    fn brackets(x: EFloat, exact: f64) -> bool {
+        x.lower_bound().to_f64().unwrap() <= exact && exact <= x.upper_bound().to_f64().unwrap()
+    }
+
+    #[test]
+    fn interval_brackets_exact_result() {
+        let a = EFloat::new(2.0, 1e-3);
+        let b = EFloat::new(0.3, 1e-6);
+        let c = EFloat::from(1.5);
+        // The exact inputs are somewhere inside the intervals rather than at the rounded values.
+        let exact_a = 2.0 + 4e-4;
+        let exact_b = b.value().to_f64().unwrap() - 5e-7;
+        let exact_c = 1.5;
+
+        let product = a * b;
+        assert!(brackets(product, exact_a * exact_b));
+        let sum = product + c;
+        assert!(brackets(sum, exact_a * exact_b + exact_c));
+        let root = sum.sqrt();
+        assert!(brackets(root, (exact_a * exact_b + exact_c).sqrt()));
+        let quotient = root / (a - b);
+        assert!(brackets(quotient, (exact_a * exact_b + exact_c).sqrt() / (exact_a - exact_b)));
+        assert!(brackets(-quotient, -(exact_a * exact_b + exact_c).sqrt() / (exact_a - exact_b)));
+        assert!(quotient.absolute_error() > 0.0);
+    }
+
+    #[test]
+    fn division_by_interval_straddling_zero() {
+        let q = EFloat::from(1.0) / EFloat::new(0.0, 1e-3);
+        assert_eq!(q.lower_bound(), FloatScalar::neg_infinity());
+        assert_eq!(q.upper_bound(), FloatScalar::infinity());
+    }
+}
@@ -0,0 +1,53 @@
+use math::common::*;
+use math::vector::{Vector3f, coordinate_system};
+use math::normal::Normal3f;
+
+// An orthonormal basis. Local coordinates are expressed relative to `x`, `y` and `z`, so a frame
+// built from a surface normal puts that normal along +z in local space.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Frame {
+    pub x: Vector3f,
+    pub y: Vector3f,
+    pub z: Vector3f,
+}
+
+impl Frame {
+    pub fn new(x: Vector3f, y: Vector3f, z: Vector3f) -> Frame {
+        Frame {
+            x: x,
+            y: y,
+            z: z,
+        }
+    }
+
+    pub fn from_normal(n: Normal3f) -> Frame {
+        let z = Vector3f::from(n).normalize();
+        let (x, y) = coordinate_system(z);
+        Frame::new(x, y, z)
+    }
+
+    pub fn to_local(&self, v: Vector3f) -> Vector3f {
+        Vector3f::new(dot(v, self.x), dot(v, self.y), dot(v, self.z))
+    }
+
+    pub fn from_local(&self, v: Vector3f) -> Vector3f {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::vector::vec3;
+
+    #[test]
+    fn local_round_trip() {
+        let n = Normal3f::new(1.0, -2.0, 0.5);
+        let frame = Frame::from_normal(n);
+        assert!(frame.to_local(Vector3f::from(n).normalize()).approx_eq(vec3(0.0, 0.0, 1.0), 1e-5));
+
+        let v = vec3(0.3, 4.0, -2.5);
+        assert!(frame.from_local(frame.to_local(v)).approx_eq(v, 1e-5));
+        assert!(frame.to_local(v).magnitude().approx_eq(v.magnitude(), 1e-5));
+    }
+}
@@ -1,9 +1,13 @@
-use num::Zero;
+use num::{Zero, Float};
 use std::convert::From;
-use math::common::{cross, dot};
-use math::vector::{Vector3f, vec3};
+use math::common::{cross, dot, InnerProductSpace, ApproxEq, format_component, format_component_exp};
+use math::vector::{Vector2f, Vector3f, vec3};
+use math::point::{Point2f, Point3f};
+use math::normal::Normal3f;
+use math::quaternion::Quaternion;
 use math::scalar::*;
 use std::ops::*;
+use std::fmt;
 
 type Matrix2x2Array = [[FloatScalar; 2]; 2];
 type Matrix3x3Array = [[FloatScalar; 3]; 3];
@@ -24,6 +28,86 @@ pub struct Matrix4x4 {
     m: Matrix4x4Array,
 }
 
+// Formats each row as `[a, b, c]` on its own line, right-aligning the columns.
+fn fmt_rows(f: &mut fmt::Formatter, rows: &[&[FloatScalar]],
+            format: fn(FloatScalar, Option<usize>) -> String) -> fmt::Result {
+    let cells: Vec<Vec<String>> = rows.iter()
+        .map(|row| row.iter().map(|&x| format(x, f.precision())).collect())
+        .collect();
+    let width = cells.iter().flat_map(|row| row.iter()).map(|c| c.len()).max().unwrap_or(0);
+
+    for (i, row) in cells.iter().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        let padded: Vec<String> = row.iter().map(|c| format!("{:>1$}", c, width)).collect();
+        write!(f, "[{}]", padded.join(", "))?;
+    }
+    Ok(())
+}
+
+// Generates the element-wise `Add`/`Sub` between matrices of the same size, the scalar
+// `Mul`/`Div`, and their compound assignment forms, so they are defined once for every matrix
+// size. `MulAssign` between matrices forwards to each type's own matrix product.
+macro_rules! impl_elementwise_ops {
+    ($t:ident) => {
+        impl_elementwise_op!($t, Add, add, AddAssign, add_assign, +=);
+        impl_elementwise_op!($t, Sub, sub, SubAssign, sub_assign, -=);
+        impl_scalar_op!($t, Mul, mul, MulAssign, mul_assign, *=);
+        impl_scalar_op!($t, Div, div, DivAssign, div_assign, /=);
+
+        impl MulAssign for $t {
+            fn mul_assign(&mut self, other: $t) {
+                *self = *self * other;
+            }
+        }
+    }
+}
+
+macro_rules! impl_elementwise_op {
+    ($t:ident, $op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $assign:tt) => {
+        impl $op for $t {
+            type Output = $t;
+
+            fn $f(mut self, other: $t) -> $t {
+                self $assign other;
+                self
+            }
+        }
+
+        impl $op_assign for $t {
+            fn $f_assign(&mut self, other: $t) {
+                for (row, other_row) in self.m.iter_mut().zip(other.m.iter()) {
+                    for (a, &b) in row.iter_mut().zip(other_row.iter()) {
+                        *a $assign b;
+                    }
+                }
+            }
+        }
+    }
+}
+
+macro_rules! impl_scalar_op {
+    ($t:ident, $op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $assign:tt) => {
+        impl $op<FloatScalar> for $t {
+            type Output = $t;
+
+            fn $f(mut self, t: FloatScalar) -> $t {
+                self $assign t;
+                self
+            }
+        }
+
+        impl $op_assign<FloatScalar> for $t {
+            fn $f_assign(&mut self, t: FloatScalar) {
+                for a in self.m.iter_mut().flat_map(|row| row.iter_mut()) {
+                    *a $assign t;
+                }
+            }
+        }
+    }
+}
+
 
 //
 // Matrix2x2
@@ -39,11 +123,20 @@ pub trait Matrix where
     Self: Div<FloatScalar, Output = Self> {
     fn identity() -> Self;
 
-    fn tranpose(&self) -> Self;
+    fn transpose(&self) -> Self;
+
+    #[deprecated(note = "use `transpose` instead")]
+    fn tranpose(&self) -> Self {
+        self.transpose()
+    }
 
     fn inverse(&self) -> Option<Self>;
 
     fn determinant(&self) -> FloatScalar;
+
+    fn cofactor(&self, i: usize, j: usize) -> FloatScalar;
+
+    fn adjugate(&self) -> Self;
 }
 
 impl Matrix2x2 {
@@ -63,6 +156,15 @@ impl Matrix2x2 {
 
         self.m[(i + 1) % 2][(j + 1) % 2]
     }
+
+    // Like `minor`, but returns `None` instead of panicking when an index is out of range.
+    pub fn try_minor(&self, i: usize, j: usize) -> Option<FloatScalar> {
+        if i > 1 || j > 1 {
+            None
+        } else {
+            Some(self.minor(i, j))
+        }
+    }
 }
 
 impl From<Matrix2x2Array> for Matrix2x2 {
@@ -88,45 +190,7 @@ impl Zero for Matrix2x2 {
     }
 }
 
-impl Add for Matrix2x2 {
-    type Output = Matrix2x2;
-
-    fn add(self, m: Matrix2x2) -> Matrix2x2 {
-        Matrix2x2::new(
-            self[0][0] + m[0][0], self[0][1] + m[0][1],
-            self[1][0] + m[1][0], self[1][1] + m[1][1])
-    }
-}
-
-impl Sub for Matrix2x2 {
-    type Output = Matrix2x2;
-
-    fn sub(self, m: Matrix2x2) -> Matrix2x2 {
-        Matrix2x2::new(
-            self[0][0] - m[0][0], self[0][1] - m[0][1],
-            self[1][0] - m[1][0], self[1][1] - m[1][1])
-    }
-}
-
-impl Mul<FloatScalar> for Matrix2x2 {
-    type Output = Matrix2x2;
-
-    fn mul(self, t: FloatScalar) -> Matrix2x2 {
-        Matrix2x2::new(
-            self[0][0] * t, self[0][1] * t,
-            self[1][0] * t, self[1][1] * t)
-    }
-}
-
-impl Div<FloatScalar> for Matrix2x2 {
-    type Output = Matrix2x2;
-
-    fn div(self, t: FloatScalar) -> Matrix2x2 {
-        Matrix2x2::new(
-            self[0][0] / t, self[0][1] / t,
-            self[1][0] / t, self[1][1] / t)
-    }
-}
+impl_elementwise_ops!(Matrix2x2);
 
 impl Mul for Matrix2x2 {
     type Output = Matrix2x2;
@@ -165,7 +229,7 @@ impl Matrix for Matrix2x2 {
         }
     }
 
-    fn tranpose(&self) -> Matrix2x2 {
+    fn transpose(&self) -> Matrix2x2 {
         Matrix2x2::new(self[0][0], self[1][0],
                        self[0][1], self[1][1])
     }
@@ -188,6 +252,44 @@ impl Matrix for Matrix2x2 {
     fn determinant(&self) -> FloatScalar {
         self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
     }
+
+    fn cofactor(&self, i: usize, j: usize) -> FloatScalar {
+        let sign = if (i + j) % 2 == 1 { -1.0 } else { 1.0 };
+        sign * self.minor(i, j)
+    }
+
+    fn adjugate(&self) -> Matrix2x2 {
+        Matrix2x2::new(self.m[1][1], -self.m[0][1],
+                       -self.m[1][0], self.m[0][0])
+    }
+}
+
+impl ApproxEq for Matrix2x2 {
+    type Scalar = FloatScalar;
+
+    fn approx_eq(self, other: Matrix2x2, epsilon: FloatScalar) -> bool {
+        self.m.iter().zip(other.m.iter())
+            .all(|(r1, r2)| r1.iter().zip(r2.iter()).all(|(&a, &b)| a.approx_eq(b, epsilon)))
+    }
+
+    fn relative_eq(self, other: Matrix2x2, epsilon: FloatScalar) -> bool {
+        self.m.iter().zip(other.m.iter())
+            .all(|(r1, r2)| r1.iter().zip(r2.iter()).all(|(&a, &b)| a.relative_eq(b, epsilon)))
+    }
+}
+
+impl fmt::Display for Matrix2x2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[FloatScalar]> = self.m.iter().map(|row| &row[..]).collect();
+        fmt_rows(f, &rows, format_component)
+    }
+}
+
+impl fmt::LowerExp for Matrix2x2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[FloatScalar]> = self.m.iter().map(|row| &row[..]).collect();
+        fmt_rows(f, &rows, format_component_exp)
+    }
 }
 
 
@@ -221,6 +323,14 @@ impl Matrix3x3 {
                        self.m[snd_row][fst_col], self.m[snd_row][snd_col])
     }
 
+    pub fn try_minor(&self, i: usize, j: usize) -> Option<Matrix2x2> {
+        if i > 2 || j > 2 {
+            None
+        } else {
+            Some(self.minor(i, j))
+        }
+    }
+
     pub fn from_vectors(r0: Vector3f, r1: Vector3f, r2: Vector3f) -> Matrix3x3 {
         Matrix3x3 {
             m: [
@@ -256,49 +366,7 @@ impl Zero for Matrix3x3 {
     }
 }
 
-impl Add for Matrix3x3 {
-    type Output = Matrix3x3;
-
-    fn add(self, m: Matrix3x3) -> Matrix3x3 {
-        Matrix3x3::new(
-            self[0][0] + m[0][0], self[0][1] + m[0][1], self[0][3] + m[0][3],
-            self[1][0] + m[1][0], self[1][1] + m[1][1], self[1][3] + m[1][3],
-            self[2][0] + m[2][0], self[2][1] + m[2][1], self[2][3] + m[2][3])
-    }
-}
-
-impl Sub for Matrix3x3 {
-    type Output = Matrix3x3;
-
-    fn sub(self, m: Matrix3x3) -> Matrix3x3 {
-        Matrix3x3::new(
-            self[0][0] - m[0][0], self[0][1] - m[0][1], self[0][3] - m[0][3],
-            self[1][0] - m[1][0], self[1][1] - m[1][1], self[1][3] - m[1][3],
-            self[2][0] - m[2][0], self[2][1] - m[2][1], self[2][3] - m[2][3])
-    }
-}
-
-impl Mul<FloatScalar> for Matrix3x3 {
-    type Output = Matrix3x3;
-
-    fn mul(self, t: FloatScalar) -> Matrix3x3 {
-        Matrix3x3::new(
-            self[0][0] * t, self[0][1] * t, self[0][3] * t,
-            self[1][0] * t, self[1][1] * t, self[1][3] * t,
-            self[2][0] * t, self[2][1] * t, self[2][3] * t)
-    }
-}
-
-impl Div<FloatScalar> for Matrix3x3 {
-    type Output = Matrix3x3;
-
-    fn div(self, t: FloatScalar) -> Matrix3x3 {
-        Matrix3x3::new(
-            self[0][0] / t, self[0][1] / t, self[0][3] / t,
-            self[1][0] / t, self[1][1] / t, self[1][3] / t,
-            self[2][0] / t, self[2][1] / t, self[2][3] / t)
-    }
-}
+impl_elementwise_ops!(Matrix3x3);
 
 impl Mul for Matrix3x3 {
     type Output = Matrix3x3;
@@ -319,6 +387,32 @@ impl Mul for Matrix3x3 {
     }
 }
 
+impl Mul<Vector2f> for Matrix3x3 {
+    type Output = Vector2f;
+
+    fn mul(self, v: Vector2f) -> Vector2f {
+        Vector2f::new(
+            self[0][0] * v.x + self[0][1] * v.y,
+            self[1][0] * v.x + self[1][1] * v.y)
+    }
+}
+
+impl Mul<Point2f> for Matrix3x3 {
+    type Output = Point2f;
+
+    fn mul(self, p: Point2f) -> Point2f {
+        let x = self[0][0] * p.x + self[0][1] * p.y + self[0][2];
+        let y = self[1][0] * p.x + self[1][1] * p.y + self[1][2];
+        let w = self[2][0] * p.x + self[2][1] * p.y + self[2][2];
+
+        if w == 1.0 {
+            Point2f::new(x, y)
+        } else {
+            Point2f::new(x, y) / w
+        }
+    }
+}
+
 impl Index<usize> for Matrix3x3 {
     type Output = [FloatScalar];
 
@@ -344,7 +438,7 @@ impl Matrix for Matrix3x3 {
         }
     }
 
-    fn tranpose(&self) -> Matrix3x3 {
+    fn transpose(&self) -> Matrix3x3 {
         Matrix3x3::new(
             self[0][0], self[1][0], self[2][0],
             self[0][1], self[1][1], self[2][1],
@@ -364,7 +458,7 @@ impl Matrix for Matrix3x3 {
             Some(Matrix3x3::from_vectors(
                 cross(x1, x2) * inv_det,
                 cross(x2, x0) * inv_det,
-                cross(x0, x1) * inv_det))
+                cross(x0, x1) * inv_det).transpose())
         }
     }
 
@@ -375,6 +469,49 @@ impl Matrix for Matrix3x3 {
 
         dot(x0, cross(x1, x2))
     }
+
+    fn cofactor(&self, i: usize, j: usize) -> FloatScalar {
+        let sign = if (i + j) % 2 == 1 { -1.0 } else { 1.0 };
+        sign * self.minor(i, j).determinant()
+    }
+
+    fn adjugate(&self) -> Matrix3x3 {
+        let mut adj = Matrix3x3::zero();
+        for i in 0..3 {
+            for j in 0..3 {
+                adj.m[j][i] = self.cofactor(i, j);
+            }
+        }
+        adj
+    }
+}
+
+impl ApproxEq for Matrix3x3 {
+    type Scalar = FloatScalar;
+
+    fn approx_eq(self, other: Matrix3x3, epsilon: FloatScalar) -> bool {
+        self.m.iter().zip(other.m.iter())
+            .all(|(r1, r2)| r1.iter().zip(r2.iter()).all(|(&a, &b)| a.approx_eq(b, epsilon)))
+    }
+
+    fn relative_eq(self, other: Matrix3x3, epsilon: FloatScalar) -> bool {
+        self.m.iter().zip(other.m.iter())
+            .all(|(r1, r2)| r1.iter().zip(r2.iter()).all(|(&a, &b)| a.relative_eq(b, epsilon)))
+    }
+}
+
+impl fmt::Display for Matrix3x3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[FloatScalar]> = self.m.iter().map(|row| &row[..]).collect();
+        fmt_rows(f, &rows, format_component)
+    }
+}
+
+impl fmt::LowerExp for Matrix3x3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[FloatScalar]> = self.m.iter().map(|row| &row[..]).collect();
+        fmt_rows(f, &rows, format_component_exp)
+    }
 }
 
 //
@@ -411,6 +548,239 @@ impl Matrix4x4 {
                        self.m[snd_row][fst_col], self.m[snd_row][snd_col], self.m[snd_row][trd_col],
                        self.m[trd_row][fst_col], self.m[trd_row][snd_col], self.m[trd_row][trd_col])
     }
+
+    pub fn try_minor(&self, i: usize, j: usize) -> Option<Matrix3x3> {
+        if i > 3 || j > 3 {
+            None
+        } else {
+            Some(self.minor(i, j))
+        }
+    }
+
+    // The determinant of the upper-left 3x3 block, which holds the rotation, scale and shear.
+    pub fn upper_left_determinant(&self) -> FloatScalar {
+        self.m[0][0] * (self.m[1][1] * self.m[2][2] - self.m[1][2] * self.m[2][1]) -
+        self.m[0][1] * (self.m[1][0] * self.m[2][2] - self.m[1][2] * self.m[2][0]) +
+        self.m[0][2] * (self.m[1][0] * self.m[2][1] - self.m[1][1] * self.m[2][0])
+    }
+
+    pub fn is_affine(&self) -> bool {
+        let epsilon = FloatScalar::epsilon();
+        self.m[3][0].abs() <= epsilon && self.m[3][1].abs() <= epsilon && self.m[3][2].abs() <= epsilon
+            && (self.m[3][3] - 1.0).abs() <= epsilon
+    }
+
+    pub fn extract_translation(&self) -> Vector3f {
+        vec3(self.m[0][3], self.m[1][3], self.m[2][3])
+    }
+
+    pub fn row(&self, i: usize) -> [FloatScalar; 4] {
+        self.m[i]
+    }
+
+    pub fn column(&self, j: usize) -> [FloatScalar; 4] {
+        [self.m[0][j], self.m[1][j], self.m[2][j], self.m[3][j]]
+    }
+
+    pub fn set_row(&mut self, i: usize, row: [FloatScalar; 4]) {
+        self.m[i] = row;
+    }
+
+    pub fn set_column(&mut self, j: usize, column: [FloatScalar; 4]) {
+        for (row, &x) in self.m.iter_mut().zip(column.iter()) {
+            row[j] = x;
+        }
+    }
+
+    pub fn translate(delta: Vector3f) -> Matrix4x4 {
+        Matrix4x4::new(
+            1.0, 0.0, 0.0, delta.x,
+            0.0, 1.0, 0.0, delta.y,
+            0.0, 0.0, 1.0, delta.z,
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn scale(x: FloatScalar, y: FloatScalar, z: FloatScalar) -> Matrix4x4 {
+        Matrix4x4::new(
+            x, 0.0, 0.0, 0.0,
+            0.0, y, 0.0, 0.0,
+            0.0, 0.0, z, 0.0,
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn rotate_x(theta: FloatScalar) -> Matrix4x4 {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        Matrix4x4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, cos_theta, -sin_theta, 0.0,
+            0.0, sin_theta, cos_theta, 0.0,
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn rotate_y(theta: FloatScalar) -> Matrix4x4 {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        Matrix4x4::new(
+            cos_theta, 0.0, sin_theta, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            -sin_theta, 0.0, cos_theta, 0.0,
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn rotate_z(theta: FloatScalar) -> Matrix4x4 {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        Matrix4x4::new(
+            cos_theta, -sin_theta, 0.0, 0.0,
+            sin_theta, cos_theta, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn rotate(theta: FloatScalar, axis: Vector3f) -> Matrix4x4 {
+        let a = axis.normalize();
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let one_minus_cos = 1.0 - cos_theta;
+
+        Matrix4x4::new(
+            a.x * a.x + (1.0 - a.x * a.x) * cos_theta,
+            a.x * a.y * one_minus_cos - a.z * sin_theta,
+            a.x * a.z * one_minus_cos + a.y * sin_theta,
+            0.0,
+
+            a.x * a.y * one_minus_cos + a.z * sin_theta,
+            a.y * a.y + (1.0 - a.y * a.y) * cos_theta,
+            a.y * a.z * one_minus_cos - a.x * sin_theta,
+            0.0,
+
+            a.x * a.z * one_minus_cos - a.y * sin_theta,
+            a.y * a.z * one_minus_cos + a.x * sin_theta,
+            a.z * a.z + (1.0 - a.z * a.z) * cos_theta,
+            0.0,
+
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    // Returns `None` when there is no view direction (`eye == target`) or `up` is parallel to it.
+    pub fn look_at(eye: Point3f, target: Point3f, up: Vector3f) -> Option<Matrix4x4> {
+        let dir = target - eye;
+        if dir.magnitude_squared() == 0.0 {
+            return None;
+        }
+        let dir = dir.normalize();
+        let right = cross(up.normalize(), dir);
+        if right.magnitude_squared() == 0.0 {
+            None
+        } else {
+            let right = right.normalize();
+            let new_up = cross(dir, right);
+
+            Some(Matrix4x4::new(
+                right.x, new_up.x, dir.x, eye.x,
+                right.y, new_up.y, dir.y, eye.y,
+                right.z, new_up.z, dir.z, eye.z,
+                0.0, 0.0, 0.0, 1.0))
+        }
+    }
+
+    pub fn perspective(fov: FloatScalar, near: FloatScalar, far: FloatScalar) -> Matrix4x4 {
+        let inv_tan = 1.0 / (fov / 2.0).tan();
+        Matrix4x4::new(
+            inv_tan, 0.0, 0.0, 0.0,
+            0.0, inv_tan, 0.0, 0.0,
+            0.0, 0.0, far / (far - near), -far * near / (far - near),
+            0.0, 0.0, 1.0, 0.0)
+    }
+
+    pub fn orthographic(near: FloatScalar, far: FloatScalar) -> Matrix4x4 {
+        Matrix4x4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0 / (far - near), -near / (far - near),
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn inverse_gauss(&self) -> Option<Matrix4x4> {
+        let mut a = self.m;
+        let mut inv = Matrix4x4::identity().m;
+
+        for col in 0..4 {
+            let mut pivot = col;
+            for row in (col + 1)..4 {
+                if a[row][col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() < FloatScalar::epsilon() {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let inv_pivot = 1.0 / a[col][col];
+            for k in 0..4 {
+                a[col][k] *= inv_pivot;
+                inv[col][k] *= inv_pivot;
+            }
+
+            for row in 0..4 {
+                if row != col {
+                    let f = a[row][col];
+                    for k in 0..4 {
+                        a[row][k] -= f * a[col][k];
+                        inv[row][k] -= f * inv[col][k];
+                    }
+                }
+            }
+        }
+
+        Some(Matrix4x4::from(inv))
+    }
+
+    pub fn transform_normal(&self, n: Normal3f) -> Option<Normal3f> {
+        self.inverse().map(|inv| {
+            Normal3f::new(
+                inv[0][0] * n.x + inv[1][0] * n.y + inv[2][0] * n.z,
+                inv[0][1] * n.x + inv[1][1] * n.y + inv[2][1] * n.z,
+                inv[0][2] * n.x + inv[1][2] * n.y + inv[2][2] * n.z)
+        })
+    }
+
+    // Splits the matrix into translation, rotation and scale so that `self = T * R * S`. The
+    // rotation is found by polar decomposition, repeatedly averaging the matrix with its inverse
+    // transpose until it converges. Returns `None` if the upper-left 3x3 block is singular, for
+    // example when one axis is scaled to zero.
+    pub fn decompose(&self) -> Option<(Vector3f, Quaternion, Matrix4x4)> {
+        let translation = self.extract_translation();
+
+        let mut m = *self;
+        for i in 0..3 {
+            m[i][3] = 0.0;
+            m[3][i] = 0.0;
+        }
+        m[3][3] = 1.0;
+
+        let mut r = m;
+        for _ in 0..100 {
+            let r_it = r.transpose().inverse()?;
+            let r_next = (r + r_it) * 0.5;
+
+            let mut norm: FloatScalar = 0.0;
+            for i in 0..3 {
+                let n = (r[i][0] - r_next[i][0]).abs() +
+                        (r[i][1] - r_next[i][1]).abs() +
+                        (r[i][2] - r_next[i][2]).abs();
+                norm = partial_max(norm, n);
+            }
+
+            r = r_next;
+            if norm <= 0.0001 {
+                break;
+            }
+        }
+
+        let rotation = Quaternion::from_rotation_matrix(&r);
+        let scale = r.inverse()? * m;
+        Some((translation, rotation, scale))
+    }
 }
 
 impl From<Matrix4x4Array> for Matrix4x4 {
@@ -438,53 +808,7 @@ impl Zero for Matrix4x4 {
     }
 }
 
-impl Add for Matrix4x4 {
-    type Output = Matrix4x4;
-
-    fn add(self, m: Matrix4x4) -> Matrix4x4 {
-        Matrix4x4::new(
-            self[0][0] + m[0][0], self[0][1] + m[0][1], self[0][3] + m[0][3], self[0][3] + m[0][3],
-            self[1][0] + m[1][0], self[1][1] + m[1][1], self[1][3] + m[1][3], self[1][3] + m[1][3],
-            self[2][0] + m[2][0], self[2][1] + m[2][1], self[2][3] + m[2][3], self[2][3] + m[2][3],
-            self[3][0] + m[3][0], self[3][1] + m[3][1], self[3][3] + m[3][3], self[3][3] + m[3][3])
-    }
-}
-
-impl Sub for Matrix4x4 {
-    type Output = Matrix4x4;
-
-    fn sub(self, m: Matrix4x4) -> Matrix4x4 {
-        Matrix4x4::new(
-            self[0][0] - m[0][0], self[0][1] - m[0][1], self[0][3] - m[0][3], self[0][3] - m[0][3],
-            self[1][0] - m[1][0], self[1][1] - m[1][1], self[1][3] - m[1][3], self[1][3] - m[1][3],
-            self[2][0] - m[2][0], self[2][1] - m[2][1], self[2][3] - m[2][3], self[2][3] - m[2][3],
-            self[3][0] - m[3][0], self[3][1] - m[3][1], self[3][3] - m[3][3], self[3][3] - m[3][3])
-    }
-}
-
-impl Mul<FloatScalar> for Matrix4x4 {
-    type Output = Matrix4x4;
-
-    fn mul(self, t: FloatScalar) -> Matrix4x4 {
-        Matrix4x4::new(
-            self[0][0] * t, self[0][1] * t, self[0][3] * t, self[0][3] * t,
-            self[1][0] * t, self[1][1] * t, self[1][3] * t, self[1][3] * t,
-            self[2][0] * t, self[2][1] * t, self[2][3] * t, self[2][3] * t,
-            self[3][0] * t, self[3][1] * t, self[3][3] * t, self[3][3] * t)
-    }
-}
-
-impl Div<FloatScalar> for Matrix4x4 {
-    type Output = Matrix4x4;
-
-    fn div(self, t: FloatScalar) -> Matrix4x4 {
-        Matrix4x4::new(
-            self[0][0] / t, self[0][1] / t, self[0][3] / t, self[0][3] / t,
-            self[1][0] / t, self[1][1] / t, self[1][3] / t, self[1][3] / t,
-            self[2][0] / t, self[2][1] / t, self[2][3] / t, self[2][3] / t,
-            self[3][0] / t, self[3][1] / t, self[3][3] / t, self[3][3] / t)
-    }
-}
+impl_elementwise_ops!(Matrix4x4);
 
 impl Mul for Matrix4x4 {
     type Output = Matrix4x4;
@@ -513,6 +837,34 @@ impl Mul for Matrix4x4 {
     }
 }
 
+impl Mul<Vector3f> for Matrix4x4 {
+    type Output = Vector3f;
+
+    fn mul(self, v: Vector3f) -> Vector3f {
+        Vector3f::new(
+            self[0][0] * v.x + self[0][1] * v.y + self[0][2] * v.z,
+            self[1][0] * v.x + self[1][1] * v.y + self[1][2] * v.z,
+            self[2][0] * v.x + self[2][1] * v.y + self[2][2] * v.z)
+    }
+}
+
+impl Mul<Point3f> for Matrix4x4 {
+    type Output = Point3f;
+
+    fn mul(self, p: Point3f) -> Point3f {
+        let x = self[0][0] * p.x + self[0][1] * p.y + self[0][2] * p.z + self[0][3];
+        let y = self[1][0] * p.x + self[1][1] * p.y + self[1][2] * p.z + self[1][3];
+        let z = self[2][0] * p.x + self[2][1] * p.y + self[2][2] * p.z + self[2][3];
+        let w = self[3][0] * p.x + self[3][1] * p.y + self[3][2] * p.z + self[3][3];
+
+        if w == 1.0 {
+            Point3f::new(x, y, z)
+        } else {
+            Point3f::new(x, y, z) / w
+        }
+    }
+}
+
 impl Index<usize> for Matrix4x4 {
     type Output = [FloatScalar];
 
@@ -539,7 +891,7 @@ impl Matrix for Matrix4x4 {
         }
     }
 
-    fn tranpose(&self) -> Matrix4x4 {
+    fn transpose(&self) -> Matrix4x4 {
         Matrix4x4::new(
             self[0][0], self[1][0], self[2][0], self[3][0],
             self[0][1], self[1][1], self[2][1], self[3][1],
@@ -552,28 +904,7 @@ impl Matrix for Matrix4x4 {
         if det == 0.0 {
             None
         } else {
-            let inv_det = 1.0 / det;
-            Some(Matrix4x4::new(
-                self.minor(0, 0).determinant() * inv_det,
-                self.minor(0, 1).determinant() * inv_det,
-                self.minor(0, 2).determinant() * inv_det,
-                self.minor(0, 3).determinant() * inv_det,
-
-                self.minor(1, 0).determinant() * inv_det,
-                self.minor(1, 1).determinant() * inv_det,
-                self.minor(1, 2).determinant() * inv_det,
-                self.minor(1, 3).determinant() * inv_det,
-
-                self.minor(2, 0).determinant() * inv_det,
-                self.minor(2, 1).determinant() * inv_det,
-                self.minor(2, 2).determinant() * inv_det,
-                self.minor(2, 3).determinant() * inv_det,
-
-                self.minor(3, 0).determinant() * inv_det,
-                self.minor(3, 1).determinant() * inv_det,
-                self.minor(3, 2).determinant() * inv_det,
-                self.minor(3, 3).determinant() * inv_det,
-            ))
+            Some(self.adjugate() * (1.0 / det))
         }
     }
 
@@ -583,4 +914,387 @@ impl Matrix for Matrix4x4 {
             + self.m[0][2] * self.minor(0, 2).determinant()
             - self.m[0][3] * self.minor(0, 3).determinant()
     }
+
+    fn cofactor(&self, i: usize, j: usize) -> FloatScalar {
+        let sign = if (i + j) % 2 == 1 { -1.0 } else { 1.0 };
+        sign * self.minor(i, j).determinant()
+    }
+
+    fn adjugate(&self) -> Matrix4x4 {
+        let mut adj = Matrix4x4::zero();
+        for i in 0..4 {
+            for j in 0..4 {
+                adj.m[j][i] = self.cofactor(i, j);
+            }
+        }
+        adj
+    }
+}
+
+impl ApproxEq for Matrix4x4 {
+    type Scalar = FloatScalar;
+
+    fn approx_eq(self, other: Matrix4x4, epsilon: FloatScalar) -> bool {
+        self.m.iter().zip(other.m.iter())
+            .all(|(r1, r2)| r1.iter().zip(r2.iter()).all(|(&a, &b)| a.approx_eq(b, epsilon)))
+    }
+
+    fn relative_eq(self, other: Matrix4x4, epsilon: FloatScalar) -> bool {
+        self.m.iter().zip(other.m.iter())
+            .all(|(r1, r2)| r1.iter().zip(r2.iter()).all(|(&a, &b)| a.relative_eq(b, epsilon)))
+    }
+}
+
+impl fmt::Display for Matrix4x4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[FloatScalar]> = self.m.iter().map(|row| &row[..]).collect();
+        fmt_rows(f, &rows, format_component)
+    }
+}
+
+impl fmt::LowerExp for Matrix4x4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<&[FloatScalar]> = self.m.iter().map(|row| &row[..]).collect();
+        fmt_rows(f, &rows, format_component_exp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::test_util::max_difference;
+
+    #[test]
+    fn matrix3x3_add_and_sub() {
+        let a = Matrix3x3::new(1.0, 2.0, 3.0,
+                               4.0, 5.0, 6.0,
+                               7.0, 8.0, 9.0);
+        let b = Matrix3x3::new(9.0, 8.0, 7.0,
+                               6.0, 5.0, 4.0,
+                               3.0, 2.0, 0.5);
+
+        assert_eq!(a + b, Matrix3x3::new(10.0, 10.0, 10.0,
+                                         10.0, 10.0, 10.0,
+                                         10.0, 10.0, 9.5));
+        assert_eq!(a - b, Matrix3x3::new(-8.0, -6.0, -4.0,
+                                         -2.0, 0.0, 2.0,
+                                         4.0, 6.0, 8.5));
+    }
+
+    fn counting_matrix4x4() -> Matrix4x4 {
+        Matrix4x4::new(1.0, 2.0, 3.0, 4.0,
+                       5.0, 6.0, 7.0, 8.0,
+                       9.0, 10.0, 11.0, 12.0,
+                       13.0, 14.0, 15.0, 16.0)
+    }
+
+    #[test]
+    fn matrix4x4_scalar_mul_and_div() {
+        let m = counting_matrix4x4();
+        assert_eq!(m * 2.0, Matrix4x4::new(2.0, 4.0, 6.0, 8.0,
+                                           10.0, 12.0, 14.0, 16.0,
+                                           18.0, 20.0, 22.0, 24.0,
+                                           26.0, 28.0, 30.0, 32.0));
+        assert_eq!(m / 4.0, Matrix4x4::new(0.25, 0.5, 0.75, 1.0,
+                                           1.25, 1.5, 1.75, 2.0,
+                                           2.25, 2.5, 2.75, 3.0,
+                                           3.25, 3.5, 3.75, 4.0));
+    }
+
+    #[test]
+    fn matrix4x4_add_and_sub() {
+        let a = counting_matrix4x4();
+        let b = Matrix4x4::new(0.5, 0.0, 2.0, -1.0,
+                               0.0, 0.5, -2.0, 1.0,
+                               1.0, 1.0, 3.0, 3.0,
+                               -4.0, 4.0, 5.0, 6.0);
+
+        assert_eq!(a + b, Matrix4x4::new(1.5, 2.0, 5.0, 3.0,
+                                         5.0, 6.5, 5.0, 9.0,
+                                         10.0, 11.0, 14.0, 15.0,
+                                         9.0, 18.0, 20.0, 22.0));
+        assert_eq!(a - b, Matrix4x4::new(0.5, 2.0, 1.0, 5.0,
+                                         5.0, 5.5, 9.0, 7.0,
+                                         8.0, 9.0, 8.0, 9.0,
+                                         17.0, 10.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn matrix3x3_inverse() {
+        let m = Matrix3x3::new(2.0, 1.0, 0.0,
+                               0.0, 3.0, 1.0,
+                               1.0, 0.0, 4.0);
+        let inv = m.inverse().unwrap();
+        assert_matrix_approx_eq!(m * inv, Matrix3x3::identity());
+        assert_matrix_approx_eq!(inv * m, Matrix3x3::identity());
+    }
+
+    #[test]
+    fn matrix4x4_inverse() {
+        let m = Matrix4x4::new(2.0, 1.0, 0.0, 3.0,
+                               0.0, 3.0, 1.0, -1.0,
+                               1.0, 0.0, 4.0, 2.0,
+                               0.0, 2.0, 0.0, 1.0);
+        let inv = m.inverse().unwrap();
+        assert_matrix_approx_eq!(m * inv, Matrix4x4::identity());
+        assert_matrix_approx_eq!(inv * m, Matrix4x4::identity());
+    }
+
+    #[test]
+    fn generated_operators_for_every_size() {
+        let a = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        let b = Matrix2x2::new(4.0, 3.0, 2.0, 0.5);
+        assert_eq!(a + b, Matrix2x2::new(5.0, 5.0, 5.0, 4.5));
+        assert_eq!(a - b, Matrix2x2::new(-3.0, -1.0, 1.0, 3.5));
+        assert_eq!(a * 3.0, Matrix2x2::new(3.0, 6.0, 9.0, 12.0));
+        assert_eq!(a / 2.0, Matrix2x2::new(0.5, 1.0, 1.5, 2.0));
+
+        let a = Matrix3x3::new(1.0, 2.0, 3.0,
+                               4.0, 5.0, 6.0,
+                               7.0, 8.0, 9.0);
+        let b = Matrix3x3::identity() * 2.0;
+        assert_eq!(a + b, Matrix3x3::new(3.0, 2.0, 3.0,
+                                         4.0, 7.0, 6.0,
+                                         7.0, 8.0, 11.0));
+        assert_eq!(a - b, Matrix3x3::new(-1.0, 2.0, 3.0,
+                                         4.0, 3.0, 6.0,
+                                         7.0, 8.0, 7.0));
+        assert_eq!(a * -1.0, Matrix3x3::new(-1.0, -2.0, -3.0,
+                                            -4.0, -5.0, -6.0,
+                                            -7.0, -8.0, -9.0));
+        assert_eq!(a / 2.0, Matrix3x3::new(0.5, 1.0, 1.5,
+                                           2.0, 2.5, 3.0,
+                                           3.5, 4.0, 4.5));
+
+        let a = counting_matrix4x4();
+        let b = Matrix4x4::identity() * 2.0;
+        assert_eq!(a + b, Matrix4x4::new(3.0, 2.0, 3.0, 4.0,
+                                         5.0, 8.0, 7.0, 8.0,
+                                         9.0, 10.0, 13.0, 12.0,
+                                         13.0, 14.0, 15.0, 18.0));
+        assert_eq!(a - b, Matrix4x4::new(-1.0, 2.0, 3.0, 4.0,
+                                         5.0, 4.0, 7.0, 8.0,
+                                         9.0, 10.0, 9.0, 12.0,
+                                         13.0, 14.0, 15.0, 14.0));
+        assert_eq!(a * 0.5, a / 2.0);
+    }
+
+    #[test]
+    fn decompose_recovers_trs() {
+        let t = vec3(1.0, -2.0, 3.0);
+        let r = Matrix4x4::rotate(0.7, vec3(1.0, 2.0, -0.5));
+        let s = Matrix4x4::scale(2.0, 3.0, 0.5);
+
+        let (translation, rotation, scale) = (Matrix4x4::translate(t) * r * s).decompose().unwrap();
+        assert_eq!(translation, t);
+        assert_matrix_approx_eq!(rotation.to_matrix(), r, 1e-4);
+        assert_matrix_approx_eq!(scale, s, 1e-4);
+    }
+
+    #[test]
+    fn decompose_singular_matrix() {
+        assert!(Matrix4x4::scale(1.0, 0.0, 1.0).decompose().is_none());
+    }
+
+    #[test]
+    fn look_at_forward_column() {
+        let m = Matrix4x4::look_at(Point3f::new(0.0, 0.0, -5.0), Point3f::new(0.0, 0.0, 0.0), Vector3f::unit_y()).unwrap();
+        assert_eq!(m.column(2), [0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(m.column(3), [0.0, 0.0, -5.0, 1.0]);
+    }
+
+    #[test]
+    fn look_at_degenerate() {
+        let eye = Point3f::new(1.0, 2.0, 3.0);
+        assert!(Matrix4x4::look_at(eye, eye, Vector3f::unit_y()).is_none());
+        assert!(Matrix4x4::look_at(eye, Point3f::new(1.0, 5.0, 3.0), Vector3f::unit_y()).is_none());
+    }
+
+    #[test]
+    fn matrix4x4_transforms_vectors_and_points() {
+        let m = Matrix4x4::translate(vec3(1.0, 2.0, 3.0));
+        assert_eq!(m * vec3(1.0, 1.0, 1.0), vec3(1.0, 1.0, 1.0));
+        assert!(m * Point3f::new(1.0, 1.0, 1.0) == Point3f::new(2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn matrix4x4_point_w_divide() {
+        let m = Matrix4x4::perspective(90.0.to_radians(), 1.0, 10.0);
+        let p = m * Point3f::new(1.0, 1.0, 5.0);
+        assert!(p.approx_eq(Point3f::new(0.2, 0.2, 8.0 / 9.0), 1e-6));
+    }
+
+    #[test]
+    fn transform_normal_stays_perpendicular() {
+        let m = Matrix4x4::scale(1.0, 2.0, 1.0);
+        let tangent = vec3(1.0, 1.0, 0.0);
+        let n = Normal3f::new(1.0, -1.0, 0.0);
+        assert_eq!(dot(tangent, n), 0.0);
+
+        let n = m.transform_normal(n).unwrap();
+        assert!(dot(m * tangent, n).approx_eq(0.0, 1e-6));
+        assert!(n.approx_eq(Normal3f::new(1.0, -0.5, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn rotate_and_scale_constructors() {
+        assert!((Matrix4x4::rotate_z(radians(90.0)) * Vector3f::unit_x()).approx_eq(Vector3f::unit_y(), 1e-6));
+        assert!((Matrix4x4::rotate_x(radians(90.0)) * Vector3f::unit_y()).approx_eq(Vector3f::unit_z(), 1e-6));
+        assert!((Matrix4x4::rotate_y(radians(90.0)) * Vector3f::unit_z()).approx_eq(Vector3f::unit_x(), 1e-6));
+        assert_matrix_approx_eq!(Matrix4x4::rotate(radians(90.0), Vector3f::unit_z()), Matrix4x4::rotate_z(radians(90.0)));
+
+        let s = Matrix4x4::scale(2.0, 4.0, 0.5);
+        assert_eq!(s * s.inverse().unwrap(), Matrix4x4::identity());
+        assert!(Matrix4x4::translate(vec3(1.0, 2.0, 3.0)) * Point3f::new(0.0, 0.0, 0.0) == Point3f::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn projections_map_near_and_far_to_unit_depth() {
+        let (near, far) = (0.5, 20.0);
+        for m in &[Matrix4x4::perspective(radians(60.0), near, far), Matrix4x4::orthographic(near, far)] {
+            assert!((*m * Point3f::new(0.25, -0.25, near)).z.approx_eq(0.0, 1e-6));
+            assert!((*m * Point3f::new(3.0, 2.0, far)).z.approx_eq(1.0, 1e-6));
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn tranpose_matches_transpose() {
+        let m = counting_matrix4x4();
+        assert_eq!(m.tranpose(), m.transpose());
+        assert_eq!(m.transpose()[0], [1.0, 5.0, 9.0, 13.0]);
+    }
+
+    #[test]
+    fn matrix_approx_eq_tolerance() {
+        let a = Matrix3x3::identity();
+        let mut b = a;
+        b[1][2] = 1e-7;
+        assert!(a != b);
+        assert!(a.approx_eq(b, 1e-5));
+        assert!(!a.approx_eq(b, 1e-9));
+    }
+
+    #[test]
+    fn adjugate_is_scaled_inverse() {
+        let m = Matrix2x2::new(2.0, 1.0, -1.0, 3.0);
+        assert_matrix_approx_eq!(m.adjugate(), m.inverse().unwrap() * m.determinant());
+
+        let m = Matrix3x3::new(2.0, 1.0, 0.0,
+                               0.0, 3.0, 1.0,
+                               1.0, 0.0, 4.0);
+        assert_matrix_approx_eq!(m.adjugate(), m.inverse().unwrap() * m.determinant(), 1e-4);
+
+        let m = Matrix4x4::new(2.0, 1.0, 0.0, 3.0,
+                               0.0, 3.0, 1.0, -1.0,
+                               1.0, 0.0, 4.0, 2.0,
+                               0.0, 2.0, 0.0, 1.0);
+        assert_matrix_approx_eq!(m.adjugate(), m.inverse().unwrap() * m.determinant(), 1e-4);
+        assert_eq!(m.cofactor(0, 1), -m.minor(0, 1).determinant());
+    }
+
+    #[test]
+    fn inverse_gauss_beats_adjugate_when_ill_conditioned() {
+        // The second and third rows are nearly multiples of the first.
+        let m = Matrix4x4::new(1.0, 2.0, 3.0, 4.0,
+                               2.0, 4.0001, 6.0, 8.0,
+                               3.0, 6.0, 9.0001, 12.0,
+                               1.0, 0.0, 0.0, 1.0);
+        let gauss_error = max_difference(&(m * m.inverse_gauss().unwrap()), &Matrix4x4::identity());
+        let adjugate_error = max_difference(&(m * m.inverse().unwrap()), &Matrix4x4::identity());
+        assert!(gauss_error < adjugate_error, "gauss {} vs adjugate {}", gauss_error, adjugate_error);
+
+        let m = Matrix4x4::translate(vec3(1.0, 2.0, 3.0)) * Matrix4x4::scale(2.0, 4.0, 8.0);
+        assert_matrix_approx_eq!(m.inverse_gauss().unwrap(), m.inverse().unwrap());
+        assert!(Matrix4x4::scale(1.0, 0.0, 1.0).inverse_gauss().is_none());
+    }
+
+    #[test]
+    fn matrix3x3_transforms_2d_vectors_and_points() {
+        // A 90° rotation followed by a translation of (1, 2).
+        let m = Matrix3x3::new(0.0, -1.0, 1.0,
+                               1.0, 0.0, 2.0,
+                               0.0, 0.0, 1.0);
+        assert_eq!(m * Vector2f::new(1.0, 0.0), Vector2f::new(0.0, 1.0));
+        assert!(m * Point2f::new(1.0, 0.0) == Point2f::new(1.0, 3.0));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut m = Matrix4x4::translate(vec3(1.0, 2.0, 3.0)) * Matrix4x4::scale(2.0, 2.0, 2.0);
+        assert_eq!(m.column(3), [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(m.row(0), [2.0, 0.0, 0.0, 1.0]);
+
+        m.set_column(3, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(m, Matrix4x4::scale(2.0, 2.0, 2.0));
+        m.set_row(3, [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(m.row(3), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn upper_left_determinant_matches_minor() {
+        let m = Matrix4x4::new(2.0, 1.0, 0.0, 3.0,
+                               0.0, 3.0, 1.0, -1.0,
+                               1.0, 0.0, 4.0, 2.0,
+                               0.0, 2.0, 0.0, 1.0);
+        assert_eq!(m.upper_left_determinant(), m.minor(3, 3).determinant());
+        assert_eq!(Matrix4x4::scale(2.0, 3.0, -1.0).upper_left_determinant(), -6.0);
+    }
+
+    #[test]
+    fn affine_matrices() {
+        let m = Matrix4x4::translate(vec3(1.0, -2.0, 3.0));
+        assert!(m.is_affine());
+        assert_eq!(m.extract_translation(), vec3(1.0, -2.0, 3.0));
+        assert!(!Matrix4x4::perspective(radians(60.0), 1.0, 10.0).is_affine());
+    }
+
+    #[test]
+    fn try_minor_for_every_size() {
+        let m2 = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(m2.try_minor(0, 1), Some(m2.minor(0, 1)));
+        assert_eq!(m2.try_minor(2, 0), None);
+
+        let m3 = Matrix3x3::new(1.0, 2.0, 3.0,
+                                4.0, 5.0, 6.0,
+                                7.0, 8.0, 9.0);
+        assert_eq!(m3.try_minor(1, 2), Some(m3.minor(1, 2)));
+        assert_eq!(m3.try_minor(0, 3), None);
+
+        let m4 = counting_matrix4x4();
+        assert_eq!(m4.try_minor(3, 0), Some(m4.minor(3, 0)));
+        assert_eq!(m4.try_minor(4, 4), None);
+    }
+
+    #[test]
+    fn compound_assignment_identities() {
+        let m = counting_matrix4x4();
+        let mut n = m;
+        n *= Matrix4x4::identity();
+        assert_eq!(n, m);
+        n += Matrix4x4::zero();
+        assert_eq!(n, m);
+        n -= Matrix4x4::zero();
+        assert_eq!(n, m);
+
+        n *= 2.0;
+        assert_eq!(n, m * 2.0);
+        n /= 2.0;
+        assert_eq!(n, m);
+
+        let mut m2 = Matrix2x2::new(1.0, 2.0, 3.0, 4.0);
+        m2 += Matrix2x2::identity();
+        assert_eq!(m2, Matrix2x2::new(2.0, 2.0, 3.0, 5.0));
+        let mut m3 = Matrix3x3::identity();
+        m3 -= Matrix3x3::identity();
+        assert!(m3.is_zero());
+    }
+
+    #[test]
+    fn formats_as_aligned_rows() {
+        let m = Matrix2x2::new(1.0, -2.5, 10.0, 0.25);
+        assert_eq!(format!("{}", m), "[   1, -2.5]\n[  10, 0.25]");
+        assert_eq!(format!("{:.2}", m), "[ 1.00, -2.50]\n[10.00,  0.25]");
+        assert_eq!(format!("{:e}", m), "[   1e0, -2.5e0]\n[   1e1, 2.5e-1]");
+    }
 }
@@ -1,7 +1,17 @@
+#[cfg(test)]
+#[macro_use]
+mod test_util;
 pub mod scalar;
 pub mod vector;
 pub mod point;
 pub mod common;
 pub mod normal;
 pub mod ray;
-pub mod matrix;
\ No newline at end of file
+pub mod matrix;
+pub mod bounds;
+pub mod transform;
+pub mod quaternion;
+pub mod efloat;
+pub mod frame;
+#[cfg(feature = "serde")]
+mod serialize;
\ No newline at end of file
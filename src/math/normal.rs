@@ -1,10 +1,11 @@
-use num::Zero;
+use num::{Zero, Signed};
 use math::vector::Vector3;
 
 use std::convert::From;
 use math::common::*;
 use math::scalar::*;
 use std::ops::*;
+use std::fmt;
 
 #[derive(PartialEq, Copy, Clone)]
 pub struct Normal3<T> {
@@ -21,6 +22,26 @@ impl <T: BaseNum> Normal3<T> {
             z: z
         }
     }
+
+    pub fn as_array(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl <T: BaseNum + Signed> Normal3<T> {
+    pub fn faceforward(self, v: Vector3<T>) -> Normal3<T> {
+        face_forward(self, v)
+    }
+
+    pub fn abs_dot(self, v: Vector3<T>) -> T {
+        abs_dot(self, v)
+    }
+}
+
+impl <T: BaseFloat> Normal3<T> {
+    pub fn has_nans(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
 }
 
 impl <T: BaseNum> From<Vector3<T>> for Normal3<T> {
@@ -29,6 +50,64 @@ impl <T: BaseNum> From<Vector3<T>> for Normal3<T> {
     }
 }
 
+impl <T: BaseNum> From<[T; 3]> for Normal3<T> {
+    fn from(a: [T; 3]) -> Normal3<T> {
+        Normal3::new(a[0], a[1], a[2])
+    }
+}
+
+impl <T: BaseNum> From<Normal3<T>> for [T; 3] {
+    fn from(n: Normal3<T>) -> [T; 3] {
+        n.as_array()
+    }
+}
+
+impl <T: BaseNum> Index<usize> for Normal3<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+impl <T: BaseNum> Index<Dimension3> for Normal3<T> {
+    type Output = T;
+
+    fn index(&self, index: Dimension3) -> &T {
+        match index {
+            Dimension3::X => &self.x,
+            Dimension3::Y => &self.y,
+            Dimension3::Z => &self.z,
+        }
+    }
+}
+
+impl <T: BaseNum> IndexMut<usize> for Normal3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+impl <T: BaseNum> IndexMut<Dimension3> for Normal3<T> {
+    fn index_mut(&mut self, index: Dimension3) -> &mut T {
+        match index {
+            Dimension3::X => &mut self.x,
+            Dimension3::Y => &mut self.y,
+            Dimension3::Z => &mut self.z,
+        }
+    }
+}
+
 impl <T: BaseNum> Zero for Normal3<T> {
     fn zero() -> Normal3<T> {
         Normal3::new(T::zero(), T::zero(), T::zero())
@@ -111,6 +190,53 @@ impl <T: BaseNum> DivAssign<T> for Normal3<T> {
     }
 }
 
+impl <T: BaseNum> ComponentWise for Normal3<T> {
+    type Scalar = T;
+    type Dimension = Dimension3;
+
+    fn min_component(self) -> T {
+        partial_min(self.x, partial_min(self.y, self.z))
+    }
+
+    fn max_component(self) -> T {
+        partial_max(self.x, partial_max(self.y, self.z))
+    }
+
+    fn max_dimension(self) -> Dimension3 {
+        if self.x >= self.y && self.x >= self.z {
+            Dimension3::X
+        } else if self.y >= self.z {
+            Dimension3::Y
+        } else {
+            Dimension3::Z
+        }
+    }
+
+    fn min(self, other: Normal3<T>) -> Normal3<T> {
+        Normal3::new(partial_min(self.x, other.x), partial_min(self.y, other.y), partial_min(self.z, other.z))
+    }
+
+    fn max(self, other: Normal3<T>) -> Normal3<T> {
+        Normal3::new(partial_max(self.x, other.x), partial_max(self.y, other.y), partial_max(self.z, other.z))
+    }
+}
+
+impl <T: BaseNum + Signed> ComponentWiseSigned for Normal3<T> {
+    fn abs(self) -> Normal3<T> {
+        Normal3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+}
+
+impl <T: BaseFloat> ComponentWiseFloat for Normal3<T> {
+    fn floor(self) -> Normal3<T> {
+        Normal3::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+
+    fn ceil(self) -> Normal3<T> {
+        Normal3::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+}
+
 impl <T: BaseNum> VectorSpace for Normal3<T> {
     type Scalar = T;
 }
@@ -139,4 +265,78 @@ impl <T: BaseNum> InnerProduct<Vector3<T>> for Normal3<T> {
 
 impl <T: BaseFloat> InnerProductSpace for Normal3<T> {}
 
-pub type Normal3f = Normal3<FloatScalar>;
\ No newline at end of file
+impl <T: BaseFloat> LinearInterpolate for Normal3<T> {
+    type Scalar = T;
+}
+
+impl <T: BaseFloat> ApproxEq for Normal3<T> {
+    type Scalar = T;
+
+    fn approx_eq(self, other: Normal3<T>, epsilon: T) -> bool {
+        self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon) && self.z.approx_eq(other.z, epsilon)
+    }
+
+    fn relative_eq(self, other: Normal3<T>, epsilon: T) -> bool {
+        self.x.relative_eq(other.x, epsilon) && self.y.relative_eq(other.y, epsilon)
+            && self.z.relative_eq(other.z, epsilon)
+    }
+}
+
+impl <T: BaseNum + fmt::Display> fmt::Display for Normal3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component)
+    }
+}
+
+impl <T: BaseNum + fmt::LowerExp> fmt::LowerExp for Normal3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component_exp)
+    }
+}
+
+pub type Normal3f = Normal3<FloatScalar>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_dimension_ties_pick_the_first_axis() {
+        assert_eq!(Normal3::new(1, 1, 0).max_dimension(), Dimension3::X);
+        assert_eq!(Normal3::new(0, 0, 0).max_dimension(), Dimension3::X);
+        assert_eq!(Normal3::new(5, 5, 5).max_dimension(), Dimension3::X);
+        assert_eq!(Normal3::new(0, 2, 2).max_dimension(), Dimension3::Y);
+        assert_eq!(Normal3::new(0, 1, 2).max_dimension(), Dimension3::Z);
+    }
+
+    #[test]
+    fn index_and_component_wise() {
+        let n = Normal3::new(1.0, -2.0, -3.0);
+        assert_eq!(n[Dimension3::Z], -3.0);
+        assert_eq!(n[1], -2.0);
+        assert!(n.abs() == Normal3::new(1.0, 2.0, 3.0));
+        assert_eq!(n.min_component(), -3.0);
+        assert_eq!(n.abs().max_component(), 3.0);
+    }
+
+    #[test]
+    fn faceforward_and_abs_dot() {
+        let n = Normal3::new(0.0, 0.0, 1.0);
+        let toward_viewer = Vector3::new(0.0, 1.0, 1.0);
+        assert!(n.faceforward(toward_viewer) == n);
+        assert!(n.faceforward(-toward_viewer) == -n);
+        assert!((-n).faceforward(toward_viewer) == n);
+
+        assert_eq!(n.abs_dot(Vector3::new(1.0, 0.0, -2.0)), 2.0);
+        assert_eq!(Vector3::new(1.0, 0.0, -2.0).abs_dot(n), 2.0);
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Normal3::new(1.0, 0.0, 2.0);
+        let b = Normal3::new(0.0, 1.0, -2.0);
+        assert!(a.lerp(b, 0.5) == Normal3::new(0.5, 0.5, 0.0));
+        assert!(a.lerp(b, 0.0) == a);
+        assert!(a.lerp(b, 1.0) == b);
+    }
+}
@@ -4,6 +4,7 @@ use math::common::*;
 use math::scalar::*;
 use std::convert::From;
 use std::ops::*;
+use std::fmt;
 
 #[derive(PartialEq, Copy, Clone)]
 pub struct Point2<T> {
@@ -30,6 +31,76 @@ impl <T: BaseNum> Point3<T> {
     pub fn permute(&self, x: Dimension3, y: Dimension3, z: Dimension3) -> Point3<T> {
         Point3::new(self[x], self[y], self[z])
     }
+
+    pub fn permute_indices(&self, idx: [usize; 3]) -> Point3<T> {
+        Point3::new(self[idx[0]], self[idx[1]], self[idx[2]])
+    }
+
+    pub fn as_array(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn to_homogeneous(self) -> [T; 4] {
+        [self.x, self.y, self.z, T::one()]
+    }
+
+    // Same result as `MetricSpace::distance_squared`, but without building the difference vector.
+    pub fn distance_squared_to(&self, other: &Point3<T>) -> T {
+        let (dx, dy, dz) = (self.x - other.x, self.y - other.y, self.z - other.z);
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn barycentric(p0: Point3<T>, p1: Point3<T>, p2: Point3<T>, b0: T, b1: T, b2: T) -> Point3<T> {
+        Point3::affine(&[p0, p1, p2], &[b0, b1, b2])
+    }
+
+    // Points can't be scaled or summed directly, so the combination is built as offsets from the
+    // first point. The weights are assumed to sum to one, which leaves the first weight implied, so
+    // `weights[0]` is ignored. Panics if `points` is empty or the slices differ in length.
+    pub fn affine(points: &[Point3<T>], weights: &[T]) -> Point3<T> {
+        if points.is_empty() || points.len() != weights.len() {
+            panic!("expected one weight per point, got {} points and {} weights", points.len(), weights.len())
+        }
+
+        let p0 = points[0];
+        points[1..].iter().zip(weights[1..].iter())
+            .fold(p0, |p, (&pi, &wi)| p + (pi - p0) * wi)
+    }
+}
+
+impl <T: BaseFloat> Point3<T> {
+    pub fn from_homogeneous(p: [T; 4]) -> Point3<T> {
+        let point = Point3::new(p[0], p[1], p[2]);
+        if p[3] == T::one() {
+            point
+        } else {
+            point / p[3]
+        }
+    }
+
+    pub fn has_nans(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    pub fn clamp(self, lo: T, hi: T) -> Point3<T> {
+        Point3::new(clamp(self.x, lo, hi), clamp(self.y, lo, hi), clamp(self.z, lo, hi))
+    }
+
+    pub fn saturate(self) -> Point3<T> {
+        self.clamp(T::zero(), T::one())
+    }
+
+    pub fn floor_to_i32(self) -> Point3<IntScalar> {
+        Point3::new(saturating_to_int(self.x.floor()), saturating_to_int(self.y.floor()), saturating_to_int(self.z.floor()))
+    }
+
+    pub fn ceil_to_i32(self) -> Point3<IntScalar> {
+        Point3::new(saturating_to_int(self.x.ceil()), saturating_to_int(self.y.ceil()), saturating_to_int(self.z.ceil()))
+    }
+
+    pub fn round_to_i32(self) -> Point3<IntScalar> {
+        Point3::new(saturating_to_int(self.x.round()), saturating_to_int(self.y.round()), saturating_to_int(self.z.round()))
+    }
 }
 
 impl <T: BaseNum> From<T> for Point3<T> {
@@ -44,6 +115,18 @@ impl <T: BaseNum> From<Vector3<T>> for Point3<T> {
     }
 }
 
+impl <T: BaseNum> From<[T; 3]> for Point3<T> {
+    fn from(a: [T; 3]) -> Point3<T> {
+        Point3::new(a[0], a[1], a[2])
+    }
+}
+
+impl <T: BaseNum> From<Point3<T>> for [T; 3] {
+    fn from(p: Point3<T>) -> [T; 3] {
+        p.as_array()
+    }
+}
+
 impl <T: BaseNum> Index<usize> for Point3<T> {
     type Output = T;
 
@@ -69,6 +152,27 @@ impl <T: BaseNum> Index<Dimension3> for Point3<T> {
     }
 }
 
+impl <T: BaseNum> IndexMut<usize> for Point3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+impl <T: BaseNum> IndexMut<Dimension3> for Point3<T> {
+    fn index_mut(&mut self, index: Dimension3) -> &mut T {
+        match index {
+            Dimension3::X => &mut self.x,
+            Dimension3::Y => &mut self.y,
+            Dimension3::Z => &mut self.z,
+        }
+    }
+}
+
 impl <T: BaseNum> Zero for Point3<T> {
     fn zero() -> Point3<T> {
         Point3::new(T::zero(), T::zero(), T::zero())
@@ -188,9 +292,9 @@ impl <T: BaseNum> ComponentWise for Point3<T> {
     }
 
     fn max_dimension(self) -> Dimension3 {
-        if self.x > self.y && self.x > self.z {
+        if self.x >= self.y && self.x >= self.z {
             Dimension3::X
-        } else if self.y > self.x && self.y > self.z {
+        } else if self.y >= self.z {
             Dimension3::Y
         } else {
             Dimension3::Z
@@ -234,16 +338,71 @@ impl <T: BaseFloat> LinearInterpolate for Point3<T> {
     type Scalar = T;
 }
 
+impl <T: BaseFloat> ApproxEq for Point3<T> {
+    type Scalar = T;
+
+    fn approx_eq(self, other: Point3<T>, epsilon: T) -> bool {
+        self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon) && self.z.approx_eq(other.z, epsilon)
+    }
+
+    fn relative_eq(self, other: Point3<T>, epsilon: T) -> bool {
+        self.x.relative_eq(other.x, epsilon) && self.y.relative_eq(other.y, epsilon)
+            && self.z.relative_eq(other.z, epsilon)
+    }
+}
+
+impl <T: BaseNum + fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component)
+    }
+}
+
+impl <T: BaseNum + fmt::LowerExp> fmt::LowerExp for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component_exp)
+    }
+}
+
 //
 // Point2
 //
 impl <T: BaseNum> Point2<T> {
-    fn new(x: T, y: T) -> Point2<T> {
+    pub fn new(x: T, y: T) -> Point2<T> {
         Point2 {
             x: x,
             y: y,
         }
     }
+
+    pub fn permute(&self, x: Dimension2, y: Dimension2) -> Point2<T> {
+        Point2::new(self[x], self[y])
+    }
+
+    pub fn as_array(&self) -> [T; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl <T: BaseFloat> Point2<T> {
+    pub fn clamp(self, lo: T, hi: T) -> Point2<T> {
+        Point2::new(clamp(self.x, lo, hi), clamp(self.y, lo, hi))
+    }
+
+    pub fn saturate(self) -> Point2<T> {
+        self.clamp(T::zero(), T::one())
+    }
+
+    pub fn floor_to_i32(self) -> Point2<IntScalar> {
+        Point2::new(saturating_to_int(self.x.floor()), saturating_to_int(self.y.floor()))
+    }
+
+    pub fn ceil_to_i32(self) -> Point2<IntScalar> {
+        Point2::new(saturating_to_int(self.x.ceil()), saturating_to_int(self.y.ceil()))
+    }
+
+    pub fn round_to_i32(self) -> Point2<IntScalar> {
+        Point2::new(saturating_to_int(self.x.round()), saturating_to_int(self.y.round()))
+    }
 }
 
 impl <T: BaseNum> From<T> for Point2<T> {
@@ -270,6 +429,18 @@ impl <T: BaseNum> From<Vector2<T>> for Point2<T> {
     }
 }
 
+impl <T: BaseNum> From<[T; 2]> for Point2<T> {
+    fn from(a: [T; 2]) -> Point2<T> {
+        Point2::new(a[0], a[1])
+    }
+}
+
+impl <T: BaseNum> From<Point2<T>> for [T; 2] {
+    fn from(p: Point2<T>) -> [T; 2] {
+        p.as_array()
+    }
+}
+
 impl <T: BaseNum> Index<usize> for Point2<T> {
     type Output = T;
 
@@ -293,6 +464,25 @@ impl <T: BaseNum> Index<Dimension2> for Point2<T> {
     }
 }
 
+impl <T: BaseNum> IndexMut<usize> for Point2<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+impl <T: BaseNum> IndexMut<Dimension2> for Point2<T> {
+    fn index_mut(&mut self, index: Dimension2) -> &mut T {
+        match index {
+            Dimension2::X => &mut self.x,
+            Dimension2::Y => &mut self.y,
+        }
+    }
+}
+
 impl <T: BaseNum> Zero for Point2<T> {
     fn zero() -> Point2<T> {
         Point2::new(T::zero(), T::zero())
@@ -407,7 +597,7 @@ impl <T: BaseNum> ComponentWise for Point2<T> {
     }
 
     fn max_dimension(self) -> Dimension2 {
-        if self.x > self.y {
+        if self.x >= self.y {
             Dimension2::X
         } else {
             Dimension2::Y
@@ -451,7 +641,111 @@ impl <T: BaseFloat> LinearInterpolate for Point2<T> {
     type Scalar = T;
 }
 
+impl <T: BaseFloat> ApproxEq for Point2<T> {
+    type Scalar = T;
+
+    fn approx_eq(self, other: Point2<T>, epsilon: T) -> bool {
+        self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon)
+    }
+
+    fn relative_eq(self, other: Point2<T>, epsilon: T) -> bool {
+        self.x.relative_eq(other.x, epsilon) && self.y.relative_eq(other.y, epsilon)
+    }
+}
+
+impl <T: BaseNum + fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component)
+    }
+}
+
+impl <T: BaseNum + fmt::LowerExp> fmt::LowerExp for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component_exp)
+    }
+}
+
 pub type Point2i = Point2<IntScalar>;
 pub type Point2f = Point2<FloatScalar>;
 pub type Point3i = Point3<IntScalar>;
-pub type Point3f = Point3<FloatScalar>;
\ No newline at end of file
+pub type Point3f = Point3<FloatScalar>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_mut() {
+        let mut p = Point3i::zero();
+        p[0] = 1;
+        p[1] = 2;
+        p[Dimension3::Z] = 3;
+        assert!(p == Point3::new(1, 2, 3));
+        p[Dimension3::X] = 4;
+        p[Dimension3::Y] = 5;
+        p[2] = 6;
+        assert!(p == Point3::new(4, 5, 6));
+
+        let mut p = Point2i::zero();
+        p[0] = 1;
+        p[Dimension2::Y] = 2;
+        assert!(p == Point2::new(1, 2));
+        p[Dimension2::X] = 3;
+        p[1] = 4;
+        assert!(p == Point2::new(3, 4));
+    }
+
+    #[test]
+    fn max_dimension_ties_pick_the_first_axis() {
+        assert_eq!(Point3::new(1, 1, 0).max_dimension(), Dimension3::X);
+        assert_eq!(Point3::new(0, 0, 0).max_dimension(), Dimension3::X);
+        assert_eq!(Point3::new(5, 5, 5).max_dimension(), Dimension3::X);
+        assert_eq!(Point3::new(0, 2, 2).max_dimension(), Dimension3::Y);
+        assert_eq!(Point2::new(3, 3).max_dimension(), Dimension2::X);
+    }
+
+    #[test]
+    fn homogeneous_coordinates() {
+        let p = Point3::new(1.0, 2.0, 3.0);
+        assert_eq!(p.to_homogeneous(), [1.0, 2.0, 3.0, 1.0]);
+        assert!(Point3::from_homogeneous(p.to_homogeneous()) == p);
+        assert!(Point3::from_homogeneous([2.0, 4.0, 6.0, 2.0]) == p);
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0).to_homogeneous(), [1.0, 2.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn point2_new_and_permute() {
+        let p = Point2f::new(1.5, -2.0);
+        assert_eq!((p.x, p.y), (1.5, -2.0));
+        let q = p.permute(Dimension2::Y, Dimension2::X);
+        assert_eq!((q.x, q.y), (-2.0, 1.5));
+        assert!(p.permute(Dimension2::X, Dimension2::X) == Point2::new(1.5, 1.5));
+    }
+
+    #[test]
+    fn barycentric_corners_and_centroid() {
+        let (p0, p1, p2) = (Point3::new(0.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0), Point3::new(0.0, 3.0, 3.0));
+        assert!(Point3::barycentric(p0, p1, p2, 1.0, 0.0, 0.0) == p0);
+        assert!(Point3::barycentric(p0, p1, p2, 0.0, 1.0, 0.0) == p1);
+        assert!(Point3::barycentric(p0, p1, p2, 0.0, 0.0, 1.0) == p2);
+
+        let third = 1.0 / 3.0;
+        let centroid = Point3::barycentric(p0, p1, p2, third, third, third);
+        assert!(centroid.approx_eq(Point3::new(1.0, 1.0, 1.0), 1e-6));
+        assert!(Point3::affine(&[p0, p1], &[0.5, 0.5]) == Point3::new(1.5, 0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn affine_rejects_mismatched_weights() {
+        Point3::affine(&[Point3::new(0.0, 0.0, 0.0)], &[0.5, 0.5]);
+    }
+
+    #[test]
+    fn distance_squared_to_matches_metric_space() {
+        let (a, b) = (Point3::new(1.0, 2.0, 3.0), Point3::new(-2.0, 6.0, 3.5));
+        assert_eq!(a.distance_squared_to(&b), 25.25);
+        assert_eq!(a.distance_squared_to(&b), a.distance_squared(b));
+        assert_eq!(b.distance_squared_to(&a), distance_squared(b, a));
+    }
+}
@@ -0,0 +1,173 @@
+use num::Zero;
+use math::common::*;
+use math::matrix::Matrix4x4;
+use math::vector::Vector3f;
+use math::scalar::*;
+use std::ops::*;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Quaternion {
+    pub v: Vector3f,
+    pub w: FloatScalar,
+}
+
+impl Quaternion {
+    pub fn new(v: Vector3f, w: FloatScalar) -> Quaternion {
+        Quaternion {
+            v: v,
+            w: w,
+        }
+    }
+
+    pub fn identity() -> Quaternion {
+        Quaternion::new(Vector3f::zero(), 1.0)
+    }
+
+    pub fn from_rotation_matrix(m: &Matrix4x4) -> Quaternion {
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt();
+            let w = s / 2.0;
+            let s = 0.5 / s;
+            Quaternion::new(Vector3f::new(
+                (m[2][1] - m[1][2]) * s,
+                (m[0][2] - m[2][0]) * s,
+                (m[1][0] - m[0][1]) * s), w)
+        } else {
+            let next = [1, 2, 0];
+            let mut i = 0;
+            if m[1][1] > m[0][0] {
+                i = 1;
+            }
+            if m[2][2] > m[i][i] {
+                i = 2;
+            }
+            let j = next[i];
+            let k = next[j];
+
+            let mut v = Vector3f::zero();
+            let mut s = ((m[i][i] - (m[j][j] + m[k][k])) + 1.0).sqrt();
+            v[i] = s * 0.5;
+            if s != 0.0 {
+                s = 0.5 / s;
+            }
+            v[j] = (m[j][i] + m[i][j]) * s;
+            v[k] = (m[k][i] + m[i][k]) * s;
+            Quaternion::new(v, (m[k][j] - m[j][k]) * s)
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix4x4 {
+        let (x, y, z, w) = (self.v.x, self.v.y, self.v.z, self.w);
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (x * w, y * w, z * w);
+
+        Matrix4x4::new(
+            1.0 - 2.0 * (yy + zz), 2.0 * (xy - wz), 2.0 * (xz + wy), 0.0,
+            2.0 * (xy + wz), 1.0 - 2.0 * (xx + zz), 2.0 * (yz - wx), 0.0,
+            2.0 * (xz - wy), 2.0 * (yz + wx), 1.0 - 2.0 * (xx + yy), 0.0,
+            0.0, 0.0, 0.0, 1.0)
+    }
+
+    pub fn dot(&self, other: Quaternion) -> FloatScalar {
+        dot(self.v, other.v) + self.w * other.w
+    }
+
+    pub fn normalize(&self) -> Quaternion {
+        *self / self.dot(*self).sqrt()
+    }
+
+    pub fn slerp(&self, other: Quaternion, t: FloatScalar) -> Quaternion {
+        let mut cos_theta = self.dot(other);
+        let mut other = other;
+        if cos_theta < 0.0 {
+            other = -other;
+            cos_theta = -cos_theta;
+        }
+
+        if cos_theta > 0.9995 {
+            (*self * (1.0 - t) + other * t).normalize()
+        } else {
+            let theta = partial_min(cos_theta, 1.0).acos();
+            let theta_p = theta * t;
+            let q_perp = (other - *self * cos_theta).normalize();
+            *self * theta_p.cos() + q_perp * theta_p.sin()
+        }
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Quaternion;
+
+    fn neg(self) -> Quaternion {
+        Quaternion::new(-self.v, -self.w)
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Quaternion;
+
+    fn add(self, other: Quaternion) -> Quaternion {
+        Quaternion::new(self.v + other.v, self.w + other.w)
+    }
+}
+
+impl Sub for Quaternion {
+    type Output = Quaternion;
+
+    fn sub(self, other: Quaternion) -> Quaternion {
+        Quaternion::new(self.v - other.v, self.w - other.w)
+    }
+}
+
+impl Mul<FloatScalar> for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, t: FloatScalar) -> Quaternion {
+        Quaternion::new(self.v * t, self.w * t)
+    }
+}
+
+impl Div<FloatScalar> for Quaternion {
+    type Output = Quaternion;
+
+    fn div(self, t: FloatScalar) -> Quaternion {
+        Quaternion::new(self.v / t, self.w / t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::matrix::Matrix;
+
+    #[test]
+    fn rotation_matrix_round_trip() {
+        // 30° about z has a positive trace; 170° about x takes the other branch.
+        for m in &[Matrix4x4::rotate_z(radians(30.0)), Matrix4x4::rotate_x(radians(170.0)),
+                   Matrix4x4::rotate(radians(150.0), Vector3f::new(1.0, 2.0, 3.0))] {
+            let q = Quaternion::from_rotation_matrix(m);
+            assert!((q.dot(q) - 1.0).abs() < 1e-5);
+            assert_matrix_approx_eq!(q.to_matrix(), *m);
+        }
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let a = Quaternion::identity();
+        let b = Quaternion::from_rotation_matrix(&Matrix4x4::rotate_z(radians(90.0)));
+        assert_matrix_approx_eq!(a.slerp(b, 0.0).to_matrix(), Matrix4x4::identity());
+        assert_matrix_approx_eq!(a.slerp(b, 0.5).to_matrix(), Matrix4x4::rotate_z(radians(45.0)));
+        assert_matrix_approx_eq!(a.slerp(b, 1.0).to_matrix(), Matrix4x4::rotate_z(radians(90.0)));
+    }
+
+    #[test]
+    fn slerp_takes_the_short_path() {
+        // `-b` is the same rotation as `b`; interpolating towards it must not go the long way round.
+        let a = Quaternion::identity();
+        let b = Quaternion::from_rotation_matrix(&Matrix4x4::rotate_z(radians(90.0)));
+        assert_matrix_approx_eq!(a.slerp(-b, 0.5).to_matrix(), Matrix4x4::rotate_z(radians(45.0)));
+        assert_matrix_approx_eq!(a.slerp(-b, 1.0).to_matrix(), Matrix4x4::rotate_z(radians(90.0)));
+    }
+}
@@ -1,17 +1,22 @@
 use num::{Zero, Float};
 use math::vector::Vector3f;
 use math::point::Point3f;
+use math::normal::Normal3f;
+use math::common::*;
 use math::scalar::FloatScalar;
 use std::convert::From;
 
 #[derive(PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ray {
     pub origin: Point3f,
     pub direction: Vector3f,
+    #[cfg_attr(feature = "serde", serde(with = "::math::serialize::infinite_as_null"))]
     pub tmax: FloatScalar,
     pub time: FloatScalar,
 }
 
+#[derive(PartialEq, Copy, Clone)]
 pub struct RayDifferential {
     pub ray: Ray,
     pub rx_origin: Option<Point3f>,
@@ -22,6 +27,7 @@ pub struct RayDifferential {
 
 impl Ray {
     pub fn new(origin: Point3f, direction: Vector3f) -> Ray {
+        debug_assert!(!origin.has_nans() && !direction.has_nans());
         Ray {
             origin: origin,
             direction: direction,
@@ -64,8 +70,21 @@ impl RayDifferential {
         }
     }
 
+    // Builds the differentials from rays through the neighbouring samples in x and y.
+    pub fn from_camera_samples(main: Ray, dx: Ray, dy: Ray) -> RayDifferential {
+        RayDifferential {
+            ray: main,
+            rx_origin: Some(dx.origin),
+            ry_origin: Some(dy.origin),
+            rx_direction: Some(dx.direction),
+            ry_direction: Some(dy.direction),
+        }
+    }
+
     pub fn with_differentials(&self, rx_origin: Point3f, rx_direction: Vector3f, ry_origin: Point3f,
             ry_direction: Vector3f) -> RayDifferential {
+        debug_assert!(!rx_origin.has_nans() && !rx_direction.has_nans());
+        debug_assert!(!ry_origin.has_nans() && !ry_direction.has_nans());
         RayDifferential {
             ray: self.ray,
             rx_origin: Some(rx_origin),
@@ -89,6 +108,63 @@ impl RayDifferential {
         self.rx_origin.is_some() && self.ry_origin.is_some() && self.rx_direction.is_some() 
             && self.ry_direction.is_some()
     }
+
+    pub fn clear_differentials(&mut self) {
+        self.rx_origin = None;
+        self.ry_origin = None;
+        self.rx_direction = None;
+        self.ry_direction = None;
+    }
+
+    pub fn x_differential(&self) -> Option<(Point3f, Vector3f)> {
+        self.rx_origin.and_then(|origin| self.rx_direction.map(|dir| (origin, dir)))
+    }
+
+    pub fn y_differential(&self) -> Option<(Point3f, Vector3f)> {
+        self.ry_origin.and_then(|origin| self.ry_direction.map(|dir| (origin, dir)))
+    }
+}
+
+impl ApproxEq for Ray {
+    type Scalar = FloatScalar;
+
+    fn approx_eq(self, other: Ray, epsilon: FloatScalar) -> bool {
+        self.origin.approx_eq(other.origin, epsilon) && self.direction.approx_eq(other.direction, epsilon)
+            && self.tmax.approx_eq(other.tmax, epsilon) && self.time.approx_eq(other.time, epsilon)
+    }
+
+    fn relative_eq(self, other: Ray, epsilon: FloatScalar) -> bool {
+        self.origin.relative_eq(other.origin, epsilon) && self.direction.relative_eq(other.direction, epsilon)
+            && self.tmax.relative_eq(other.tmax, epsilon) && self.time.relative_eq(other.time, epsilon)
+    }
+}
+
+impl ApproxEq for RayDifferential {
+    type Scalar = FloatScalar;
+
+    fn approx_eq(self, other: RayDifferential, epsilon: FloatScalar) -> bool {
+        self.ray.approx_eq(other.ray, epsilon)
+            && option_eq(self.rx_origin, other.rx_origin, |a, b| a.approx_eq(b, epsilon))
+            && option_eq(self.ry_origin, other.ry_origin, |a, b| a.approx_eq(b, epsilon))
+            && option_eq(self.rx_direction, other.rx_direction, |a, b| a.approx_eq(b, epsilon))
+            && option_eq(self.ry_direction, other.ry_direction, |a, b| a.approx_eq(b, epsilon))
+    }
+
+    fn relative_eq(self, other: RayDifferential, epsilon: FloatScalar) -> bool {
+        self.ray.relative_eq(other.ray, epsilon)
+            && option_eq(self.rx_origin, other.rx_origin, |a, b| a.relative_eq(b, epsilon))
+            && option_eq(self.ry_origin, other.ry_origin, |a, b| a.relative_eq(b, epsilon))
+            && option_eq(self.rx_direction, other.rx_direction, |a, b| a.relative_eq(b, epsilon))
+            && option_eq(self.ry_direction, other.ry_direction, |a, b| a.relative_eq(b, epsilon))
+    }
+}
+
+fn option_eq<T, F: Fn(T, T) -> bool>(a: Option<T>, b: Option<T>, eq: F) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
 }
 
 impl From<Ray> for RayDifferential {
@@ -101,4 +177,84 @@ impl From<Ray> for RayDifferential {
             ry_direction: None,
         }
     }
-}
\ No newline at end of file
+}
+
+// Starts a ray at the surface point `p` heading in direction `d`, with the origin pushed off the
+// surface along `n` so the ray doesn't immediately re-hit the surface it left because of rounding
+// error. The offset grows with the magnitude of `p`, since the rounding error does too.
+pub fn spawn_ray(p: Point3f, n: Normal3f, d: Vector3f) -> Ray {
+    let epsilon = 1e-4 * (1.0 + max_component(p.abs()));
+    let offset = Vector3f::from(n) * epsilon;
+    let origin = if dot(d, n) < 0.0 { p - offset } else { p + offset };
+    Ray::new(origin, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::vector::vec3;
+    use shapes::triangle::intersect_triangle;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn new_rejects_nans() {
+        Ray::new(Point3f::new(0.0, FloatScalar::nan(), 0.0), vec3(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn spawn_ray_offsets_to_the_side_it_leaves_from() {
+        let p = Point3f::new(100.25, 200.75, 0.0);
+        let up = Normal3f::new(0.0, 0.0, 1.0);
+        assert!(spawn_ray(p, up, vec3(0.3, 0.2, 1.0)).origin.z > 0.0);
+        assert!(spawn_ray(p, up, vec3(0.0, 0.0, -1.0)).origin.z < 0.0);
+        // The side is chosen by the direction, not by which way the normal faces.
+        assert!(spawn_ray(p, -up, vec3(0.3, 0.2, 1.0)).origin.z > 0.0);
+    }
+
+    #[test]
+    fn shadow_ray_does_not_hit_its_own_surface() {
+        let (p0, p1, p2) = (Point3f::new(-1e3, -1e3, 0.0), Point3f::new(1e3, -1e3, 0.0),
+                            Point3f::new(0.0, 1e3, 0.0));
+        // A hit point that rounding error has left just below the plane.
+        let p = Point3f::new(100.25, 200.75, -1e-6);
+        let to_light = vec3(-0.5, 0.25, 1.0);
+        assert!(intersect_triangle(&Ray::new(p, to_light), p0, p1, p2).is_some());
+        assert!(intersect_triangle(&spawn_ray(p, Normal3f::new(0.0, 0.0, 1.0), to_light), p0, p1, p2).is_none());
+    }
+
+    #[test]
+    fn camera_samples_populate_and_scale_differentials() {
+        let origin = Point3f::new(0.0, 0.0, 0.0);
+        let main = Ray::new(origin, vec3(0.0, 0.0, 1.0));
+        let dx = Ray::new(Point3f::new(0.5, 0.0, 0.0), vec3(0.5, 0.0, 1.0));
+        let dy = Ray::new(Point3f::new(0.0, 0.5, 0.0), vec3(0.0, 0.5, 1.0));
+        let rd = RayDifferential::from_camera_samples(main, dx, dy);
+        assert!(rd.has_differentials());
+        assert!(rd.x_differential() == Some((dx.origin, dx.direction)));
+        assert!(rd.y_differential() == Some((dy.origin, dy.direction)));
+
+        let scaled = rd.scale_differentials(0.5);
+        assert!(scaled.ray == main);
+        assert!(scaled.x_differential() == Some((Point3f::new(0.25, 0.0, 0.0), vec3(0.25, 0.0, 1.0))));
+        assert!(scaled.y_differential() == Some((Point3f::new(0.0, 0.25, 0.0), vec3(0.0, 0.25, 1.0))));
+    }
+
+    #[test]
+    fn differentials_toggle() {
+        let mut rd = RayDifferential::new(Point3f::new(0.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0));
+        assert!(!rd.has_differentials());
+        assert!(rd.x_differential().is_none() && rd.y_differential().is_none());
+
+        let (rx_origin, rx_direction) = (Point3f::new(1.0, 0.0, 0.0), vec3(0.1, 0.0, 1.0));
+        let (ry_origin, ry_direction) = (Point3f::new(0.0, 1.0, 0.0), vec3(0.0, 0.1, 1.0));
+        rd = rd.with_differentials(rx_origin, rx_direction, ry_origin, ry_direction);
+        assert!(rd.has_differentials());
+        assert!(rd.x_differential() == Some((rx_origin, rx_direction)));
+        assert!(rd.y_differential() == Some((ry_origin, ry_direction)));
+
+        rd.clear_differentials();
+        assert!(!rd.has_differentials());
+        assert!(rd.x_differential().is_none() && rd.y_differential().is_none());
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt;
 use std::ops;
 use num;
 
-use math::common::LinearInterpolate;
+use math::common::{LinearInterpolate, ApproxEq};
 
 pub type IntScalar = i32;
 #[cfg(not(feature = "float64"))]
@@ -49,6 +49,8 @@ impl BaseInt for usize {}
 impl BaseFloat for f32 {}
 impl BaseFloat for f64 {}
 
+// If either argument is NaN the comparison fails and `partial_min`/`partial_max` return `b`. Use
+// `min_nan_safe`/`max_nan_safe` when NaN inputs are possible.
 pub fn partial_min<T: cmp::PartialOrd>(a: T, b: T) -> T {
     if a < b {
         a
@@ -65,10 +67,191 @@ pub fn partial_max<T: cmp::PartialOrd>(a: T, b: T) -> T {
     }
 }
 
+// IEEE 754 `minNum`: a NaN argument is ignored in favor of the other one, and NaN is only
+// returned when both arguments are NaN.
+pub fn min_nan_safe<T: BaseFloat>(a: T, b: T) -> T {
+    if a.is_nan() {
+        b
+    } else if b.is_nan() {
+        a
+    } else {
+        partial_min(a, b)
+    }
+}
+
+// IEEE 754 `maxNum`, with the same NaN handling as `min_nan_safe`.
+pub fn max_nan_safe<T: BaseFloat>(a: T, b: T) -> T {
+    if a.is_nan() {
+        b
+    } else if b.is_nan() {
+        a
+    } else {
+        partial_max(a, b)
+    }
+}
+
+pub fn clamp<T: cmp::PartialOrd>(x: T, low: T, high: T) -> T {
+    if x < low {
+        low
+    } else if x > high {
+        high
+    } else {
+        x
+    }
+}
+
+pub fn lerp<T: BaseFloat>(t: T, a: T, b: T) -> T {
+    (T::one() - t) * a + t * b
+}
+
+pub fn radians<T: BaseFloat>(deg: T) -> T {
+    deg.to_radians()
+}
+
+pub fn degrees<T: BaseFloat>(rad: T) -> T {
+    rad.to_degrees()
+}
+
+pub fn solve_quadratic(a: FloatScalar, b: FloatScalar, c: FloatScalar) -> Option<(FloatScalar, FloatScalar)> {
+    if a == 0.0 {
+        return if b == 0.0 {
+            None
+        } else {
+            Some((-c / b, -c / b))
+        };
+    }
+
+    let (a, b, c) = (a as f64, b as f64, c as f64);
+    let discrim = b * b - 4.0 * a * c;
+    if discrim < 0.0 {
+        return None;
+    }
+
+    let root_discrim = discrim.sqrt();
+    let q = if b < 0.0 { -0.5 * (b - root_discrim) } else { -0.5 * (b + root_discrim) };
+    let t0 = (q / a) as FloatScalar;
+    let t1 = if q == 0.0 { t0 } else { (c / q) as FloatScalar };
+
+    if t0 > t1 {
+        Some((t1, t0))
+    } else {
+        Some((t0, t1))
+    }
+}
+
+// Converts to an integer, saturating at the bounds of `IntScalar` when `x` is out of range. NaN
+// converts to zero.
+pub fn saturating_to_int<T: BaseFloat>(x: T) -> IntScalar {
+    match <IntScalar as num::NumCast>::from(x) {
+        Some(i) => i,
+        None if x.is_nan() => 0,
+        None if x > T::zero() => IntScalar::MAX,
+        None => IntScalar::MIN,
+    }
+}
+
+pub fn machine_epsilon() -> FloatScalar {
+    <FloatScalar as num::Float>::epsilon() * 0.5
+}
+
+pub fn gamma(n: IntScalar) -> FloatScalar {
+    let n = n as FloatScalar;
+    (n * machine_epsilon()) / (1.0 - n * machine_epsilon())
+}
+
+// Returns the smallest representable value greater than `v`. Positive infinity and NaN are
+// returned unchanged, and -0.0 is treated as +0.0 so the step lands on the smallest positive
+// subnormal.
+pub fn next_float_up(v: FloatScalar) -> FloatScalar {
+    if v.is_nan() || v == FloatScalar::INFINITY {
+        return v;
+    }
+
+    let v = if v == 0.0 { 0.0 } else { v };
+    let bits = v.to_bits();
+    FloatScalar::from_bits(if v >= 0.0 { bits + 1 } else { bits - 1 })
+}
+
+// Returns the largest representable value less than `v`, mirroring `next_float_up`.
+pub fn next_float_down(v: FloatScalar) -> FloatScalar {
+    if v.is_nan() || v == FloatScalar::NEG_INFINITY {
+        return v;
+    }
+
+    let v = if v == 0.0 { -0.0 } else { v };
+    let bits = v.to_bits();
+    FloatScalar::from_bits(if v <= 0.0 { bits + 1 } else { bits - 1 })
+}
+
 impl LinearInterpolate for f32 {
     type Scalar = f32;
 }
 
 impl LinearInterpolate for f64 {
     type Scalar = f64;
-}
\ No newline at end of file
+}
+
+impl <T: BaseFloat> ApproxEq for T {
+    type Scalar = T;
+
+    fn approx_eq(self, other: T, epsilon: T) -> bool {
+        self == other || (self - other).abs() <= epsilon
+    }
+
+    fn relative_eq(self, other: T, epsilon: T) -> bool {
+        self == other || (self - other).abs() <= epsilon * partial_max(self.abs(), other.abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_quadratic_roots() {
+        // (t - 1)(t - 3)
+        assert_eq!(solve_quadratic(1.0, -4.0, 3.0), Some((1.0, 3.0)));
+        // (t - 2)^2 has a single repeated root.
+        assert_eq!(solve_quadratic(1.0, -4.0, 4.0), Some((2.0, 2.0)));
+        assert_eq!(solve_quadratic(1.0, 0.0, 1.0), None);
+        assert_eq!(solve_quadratic(0.0, 2.0, -4.0), Some((2.0, 2.0)));
+        assert_eq!(solve_quadratic(0.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn next_float() {
+        assert_eq!(next_float_up(1.0), 1.0 + FloatScalar::EPSILON);
+        assert_eq!(next_float_down(1.0), 1.0 - FloatScalar::EPSILON / 2.0);
+        for &x in [1.0, -1.0, 0.5, 1e-30, -3.25e10, FloatScalar::MAX].iter() {
+            assert_eq!(next_float_down(next_float_up(x)), x);
+            assert_eq!(next_float_up(next_float_down(x)), x);
+        }
+
+        let tiny = FloatScalar::from_bits(1);
+        assert_eq!(next_float_up(0.0), tiny);
+        assert_eq!(next_float_up(-0.0), tiny);
+        assert_eq!(next_float_down(0.0), -tiny);
+        assert_eq!(next_float_down(-0.0), -tiny);
+
+        assert_eq!(next_float_up(FloatScalar::INFINITY), FloatScalar::INFINITY);
+        assert_eq!(next_float_down(FloatScalar::NEG_INFINITY), FloatScalar::NEG_INFINITY);
+        assert_eq!(next_float_up(FloatScalar::NEG_INFINITY), FloatScalar::MIN);
+        assert_eq!(next_float_down(FloatScalar::INFINITY), FloatScalar::MAX);
+        assert!(next_float_up(FloatScalar::NAN).is_nan());
+    }
+
+    #[test]
+    fn nan_safe_min_max() {
+        let nan = FloatScalar::NAN;
+        assert_eq!(min_nan_safe(nan, 1.0), 1.0);
+        assert_eq!(min_nan_safe(1.0, nan), 1.0);
+        assert_eq!(max_nan_safe(nan, 1.0), 1.0);
+        assert_eq!(max_nan_safe(1.0, nan), 1.0);
+        assert!(min_nan_safe(nan, nan).is_nan());
+
+        assert_eq!(min_nan_safe(2.0, -1.0), -1.0);
+        assert_eq!(max_nan_safe(2.0, -1.0), 2.0);
+        // The plain versions return the second argument when either is NaN.
+        assert!(partial_min(1.0, nan).is_nan());
+    }
+}
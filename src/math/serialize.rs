@@ -0,0 +1,105 @@
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use math::vector::{Vector2, Vector3};
+use math::point::{Point2, Point3};
+use math::normal::Normal3;
+use math::matrix::Matrix4x4;
+use math::scalar::*;
+
+// Vectors, points and normals are written as plain arrays, e.g. `[1.0, 2.0, 3.0]`, rather than
+// as maps of their fields.
+macro_rules! impl_serde_as_array {
+    ($t:ident, $n:expr) => {
+        impl <T: BaseNum + Serialize> Serialize for $t<T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.as_array().serialize(serializer)
+            }
+        }
+
+        impl <'de, T: BaseNum + Deserialize<'de>> Deserialize<'de> for $t<T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$t<T>, D::Error> {
+                <[T; $n]>::deserialize(deserializer).map($t::from)
+            }
+        }
+    }
+}
+
+impl_serde_as_array!(Vector2, 2);
+impl_serde_as_array!(Vector3, 3);
+impl_serde_as_array!(Point2, 2);
+impl_serde_as_array!(Point3, 3);
+impl_serde_as_array!(Normal3, 3);
+
+// Matrices are written as an array of rows.
+impl Serialize for Matrix4x4 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            row.copy_from_slice(&self[i]);
+        }
+        rows.serialize(serializer)
+    }
+}
+
+impl <'de> Deserialize<'de> for Matrix4x4 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Matrix4x4, D::Error> {
+        <[[FloatScalar; 4]; 4]>::deserialize(deserializer).map(Matrix4x4::from)
+    }
+}
+
+// JSON has no representation for infinity (serde_json writes it as `null` and then refuses to
+// read it back), so positive infinity is written as `null` and `null` is read back as infinity.
+// This keeps the default unbounded `Ray::tmax` round-trippable.
+pub mod infinite_as_null {
+    use num::Float;
+    use serde::{Serialize, Serializer, Deserialize, Deserializer};
+    use math::scalar::FloatScalar;
+
+    pub fn serialize<S: Serializer>(value: &FloatScalar, serializer: S) -> Result<S::Ok, S::Error> {
+        if *value == FloatScalar::infinity() {
+            serializer.serialize_none()
+        } else {
+            value.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FloatScalar, D::Error> {
+        Option::<FloatScalar>::deserialize(deserializer).map(|v| v.unwrap_or_else(FloatScalar::infinity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use math::matrix::Matrix4x4;
+    use math::vector::{Vector3f, vec3};
+    use math::point::Point3f;
+    use math::ray::Ray;
+
+    #[test]
+    fn matrix_round_trips() {
+        let m = Matrix4x4::translate(vec3(1.0, -2.0, 3.5)) * Matrix4x4::rotate(0.3, vec3(1.0, 1.0, 0.0));
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(serde_json::from_str::<Matrix4x4>(&json).unwrap() == m);
+    }
+
+    #[test]
+    fn vector_serializes_as_array() {
+        assert_eq!(serde_json::to_string(&vec3(1.0, 2.0, 3.0)).unwrap(), "[1.0,2.0,3.0]");
+        assert!(serde_json::from_str::<Vector3f>("[1.0,2.0,3.0]").unwrap() == vec3(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn default_ray_round_trips() {
+        let ray = Ray::new(Point3f::new(1.0, 2.0, 3.0), vec3(0.0, 1.0, 0.0));
+        let json = serde_json::to_string(&ray).unwrap();
+        assert!(json.contains("\"tmax\":null"));
+        assert!(serde_json::from_str::<Ray>(&json).unwrap() == ray);
+    }
+
+    #[test]
+    fn bounded_ray_round_trips() {
+        let ray = Ray::new(Point3f::new(1.0, 2.0, 3.0), vec3(0.0, 1.0, 0.0)).with_tmax(5.0).with_time(0.5);
+        let json = serde_json::to_string(&ray).unwrap();
+        assert!(serde_json::from_str::<Ray>(&json).unwrap() == ray);
+    }
+}
@@ -0,0 +1,65 @@
+use math::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+use math::scalar::*;
+
+// Asserts that two matrices of the same size are equal to within `epsilon` (1e-5 when omitted).
+// On failure both matrices are printed as grids along with the largest element difference.
+macro_rules! assert_matrix_approx_eq {
+    ($a:expr, $b:expr) => {
+        assert_matrix_approx_eq!($a, $b, 1e-5)
+    };
+    ($a:expr, $b:expr, $epsilon:expr) => {{
+        let (a, b, epsilon) = ($a, $b, $epsilon);
+        if !::math::common::ApproxEq::approx_eq(a, b, epsilon) {
+            panic!("matrices differ by up to {} (epsilon {})\nleft:\n{}\nright:\n{}",
+                   ::math::test_util::max_difference(&a, &b), epsilon, a, b);
+        }
+    }};
+}
+
+pub trait Elements {
+    fn elements(&self) -> Vec<FloatScalar>;
+}
+
+macro_rules! impl_elements {
+    ($t:ident, $n:expr) => {
+        impl Elements for $t {
+            fn elements(&self) -> Vec<FloatScalar> {
+                (0..$n).flat_map(|i| self[i].to_vec()).collect()
+            }
+        }
+    }
+}
+
+impl_elements!(Matrix2x2, 2);
+impl_elements!(Matrix3x3, 3);
+impl_elements!(Matrix4x4, 4);
+
+pub fn max_difference<M: Elements>(a: &M, b: &M) -> FloatScalar {
+    a.elements().iter().zip(b.elements().iter())
+        .map(|(&x, &y)| (x - y).abs())
+        .fold(0.0, partial_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use math::matrix::{Matrix, Matrix2x2};
+    use std::panic;
+
+    #[test]
+    fn passes_for_equal_matrices() {
+        assert_matrix_approx_eq!(Matrix2x2::identity(), Matrix2x2::identity());
+        assert_matrix_approx_eq!(Matrix2x2::identity(), Matrix2x2::new(1.0, 1e-6, 0.0, 1.0));
+    }
+
+    #[test]
+    fn fails_with_both_grids_and_the_difference() {
+        let result = panic::catch_unwind(|| {
+            assert_matrix_approx_eq!(Matrix2x2::identity(), Matrix2x2::new(1.0, 0.0, 0.5, 1.0));
+        });
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert_eq!(*message, "matrices differ by up to 0.5 (epsilon 0.00001)\n\
+                              left:\n[1, 0]\n[0, 1]\n\
+                              right:\n[  1,   0]\n[0.5,   1]");
+    }
+}
@@ -0,0 +1,215 @@
+use num::Float;
+use math::common::ApproxEq;
+use math::matrix::{Matrix, Matrix4x4};
+use math::ray::{Ray, RayDifferential};
+use math::bounds::Bounds3f;
+use math::quaternion::Quaternion;
+use math::vector::Vector3f;
+use math::point::Point3f;
+use math::scalar::FloatScalar;
+use std::ops::*;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Transform {
+    m: Matrix4x4,
+    m_inv: Matrix4x4,
+}
+
+impl Transform {
+    // Panics if `m` is singular. Use `from_pair` when the inverse is already known.
+    pub fn new(m: Matrix4x4) -> Transform {
+        match m.inverse() {
+            Some(m_inv) => Transform::from_pair(m, m_inv),
+            None => panic!("matrix '{:?}' is not invertible", m),
+        }
+    }
+
+    pub fn from_pair(m: Matrix4x4, m_inv: Matrix4x4) -> Transform {
+        Transform {
+            m: m,
+            m_inv: m_inv,
+        }
+    }
+
+    // Composes `translate * rotate * scale`. The inverse is built from the inverses of each part
+    // rather than by inverting the combined matrix, which keeps it precise.
+    pub fn from_trs(t: Vector3f, r: Quaternion, s: Vector3f) -> Transform {
+        let rotation = r.normalize().to_matrix();
+        let m = Matrix4x4::translate(t) * rotation * Matrix4x4::scale(s.x, s.y, s.z);
+        let m_inv = Matrix4x4::scale(1.0 / s.x, 1.0 / s.y, 1.0 / s.z) * rotation.transpose() *
+                    Matrix4x4::translate(-t);
+        Transform::from_pair(m, m_inv)
+    }
+
+    pub fn identity() -> Transform {
+        Transform::from_pair(Matrix4x4::identity(), Matrix4x4::identity())
+    }
+
+    pub fn matrix(&self) -> Matrix4x4 {
+        self.m
+    }
+
+    pub fn inverse_matrix(&self) -> Matrix4x4 {
+        self.m_inv
+    }
+
+    pub fn inverse(&self) -> Transform {
+        Transform::from_pair(self.m_inv, self.m)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.m.approx_eq(Matrix4x4::identity(), FloatScalar::epsilon())
+    }
+
+    // A negative determinant means the transform mirrors space, so shading normals need to be
+    // flipped to stay consistent with the geometry's winding.
+    pub fn swaps_handedness(&self) -> bool {
+        self.m.upper_left_determinant() < 0.0
+    }
+
+    // Points are treated as homogeneous with w = 1, so they pick up the translation and are
+    // divided through by the resulting w.
+    pub fn transform_point(&self, p: Point3f) -> Point3f {
+        self.m * p
+    }
+
+    // Vectors are treated as homogeneous with w = 0, so translation doesn't affect them.
+    pub fn transform_vector(&self, v: Vector3f) -> Vector3f {
+        self.m * v
+    }
+
+    pub fn transform_ray(&self, ray: &Ray) -> Ray {
+        Ray {
+            origin: self.m * ray.origin,
+            direction: self.m * ray.direction,
+            tmax: ray.tmax,
+            time: ray.time,
+        }
+    }
+
+    pub fn transform_ray_differential(&self, ray: &RayDifferential) -> RayDifferential {
+        RayDifferential {
+            ray: self.transform_ray(&ray.ray),
+            rx_origin: ray.rx_origin.map(|origin| self.m * origin),
+            ry_origin: ray.ry_origin.map(|origin| self.m * origin),
+            rx_direction: ray.rx_direction.map(|dir| self.m * dir),
+            ry_direction: ray.ry_direction.map(|dir| self.m * dir),
+        }
+    }
+
+    // Every corner is transformed rather than just `min` and `max`, since a rotation can move
+    // any corner to the extremes of the new box.
+    pub fn transform_bounds(&self, b: &Bounds3f) -> Bounds3f {
+        let mut corners = b.corners();
+        for corner in corners.iter_mut() {
+            *corner = self.m * *corner;
+        }
+        Bounds3f::from_points(&corners)
+    }
+}
+
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, t: Transform) -> Transform {
+        Transform::from_pair(self.m * t.m, t.m_inv * self.m_inv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::vector::vec3;
+    use math::scalar::radians;
+
+    #[test]
+    fn composed_inverse_round_trips_points() {
+        let t = Transform::new(Matrix4x4::translate(vec3(1.0, -2.0, 3.0)))
+            * Transform::new(Matrix4x4::scale(2.0, 4.0, 0.5));
+        let p = Point3f::new(1.0, 1.0, 1.0);
+        let q = t.transform_point(p);
+        assert!(q.approx_eq(Point3f::new(3.0, 2.0, 3.5), 1e-5));
+        assert!(t.inverse().transform_point(q).approx_eq(p, 1e-5));
+        assert!((t * t.inverse()).is_identity());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics_on_singular_matrix() {
+        Transform::new(Matrix4x4::scale(1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn translated_ray_points() {
+        let t = Transform::new(Matrix4x4::translate(vec3(1.0, 2.0, 3.0)));
+        let ray = Ray::new(Point3f::new(0.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0)).with_time(0.5);
+        let moved = t.transform_ray(&ray);
+        assert!(moved.point_at(2.0) == Point3f::new(1.0, 2.0, 5.0));
+        assert_eq!(moved.direction, ray.direction);
+        assert_eq!(moved.time, 0.5);
+    }
+
+    #[test]
+    fn swaps_handedness_only_for_reflections() {
+        assert!(Transform::new(Matrix4x4::scale(-1.0, 1.0, 1.0)).swaps_handedness());
+        assert!(!Transform::new(Matrix4x4::rotate_y(radians(60.0))).swaps_handedness());
+        assert!(!Transform::identity().swaps_handedness());
+    }
+
+    #[test]
+    fn is_identity() {
+        assert!(Transform::identity().is_identity());
+        assert!(Transform::new(Matrix4x4::rotate_z(0.0)).is_identity());
+        assert!(!Transform::new(Matrix4x4::translate(vec3(0.0, 1e-3, 0.0))).is_identity());
+    }
+
+    #[test]
+    fn rotated_bounds_grow() {
+        let cube = Bounds3f::new(Point3f::new(-1.0, -1.0, -1.0), Point3f::new(1.0, 1.0, 1.0));
+        let b = Transform::new(Matrix4x4::rotate_z(radians(45.0))).transform_bounds(&cube);
+        let r = FloatScalar::sqrt(2.0);
+        assert!(b.min.approx_eq(Point3f::new(-r, -r, -1.0), 1e-5));
+        assert!(b.max.approx_eq(Point3f::new(r, r, 1.0), 1e-5));
+    }
+
+    #[test]
+    fn from_trs_matches_composed_matrix() {
+        let translation = vec3(1.0, -2.0, 0.5);
+        let rotation = Matrix4x4::rotate(radians(40.0), vec3(1.0, 1.0, 0.0));
+        let t = Transform::from_trs(translation, Quaternion::from_rotation_matrix(&rotation),
+                                    vec3(2.0, 3.0, 0.5));
+        let expected = Transform::new(Matrix4x4::translate(translation) * rotation *
+                                      Matrix4x4::scale(2.0, 3.0, 0.5));
+        assert_matrix_approx_eq!(t.matrix(), expected.matrix());
+        assert_matrix_approx_eq!(t.inverse_matrix(), expected.inverse_matrix());
+
+        let p = Point3f::new(0.25, 4.0, -1.0);
+        assert!(t.inverse().transform_point(t.transform_point(p)).approx_eq(p, 1e-5));
+    }
+
+    #[test]
+    fn ray_round_trips_through_inverse() {
+        let t = Transform::new(Matrix4x4::translate(vec3(0.1, -3.0, 7.5)));
+        let ray = Ray::new(Point3f::new(1.0, 2.0, 3.0), vec3(0.3, -0.4, 0.5)).with_time(0.25);
+        let back = t.inverse().transform_ray(&t.transform_ray(&ray));
+        assert!(back.approx_eq(ray, 1e-5));
+
+        let diff = RayDifferential::from(ray)
+            .with_differentials(Point3f::new(1.1, 2.0, 3.0), vec3(0.3, -0.4, 0.6),
+                                Point3f::new(1.0, 2.1, 3.0), vec3(0.2, -0.4, 0.5));
+        let back = t.inverse().transform_ray_differential(&t.transform_ray_differential(&diff));
+        assert!(back.approx_eq(diff, 1e-5));
+        assert!(!back.approx_eq(RayDifferential::from(ray), 1e-5));
+    }
+
+    #[test]
+    fn translation_moves_points_not_vectors() {
+        let t = Transform::new(Matrix4x4::translate(vec3(1.0, 2.0, 3.0)));
+        assert!(t.transform_point(Point3f::new(1.0, 1.0, 1.0)) == Point3f::new(2.0, 3.0, 4.0));
+        assert_eq!(t.transform_vector(vec3(1.0, 1.0, 1.0)), vec3(1.0, 1.0, 1.0));
+
+        let s = Transform::new(Matrix4x4::scale(2.0, 3.0, 4.0));
+        assert!(s.transform_point(Point3f::new(1.0, 1.0, 1.0)) == Point3f::new(2.0, 3.0, 4.0));
+        assert_eq!(s.transform_vector(vec3(1.0, 1.0, 1.0)), vec3(2.0, 3.0, 4.0));
+    }
+}
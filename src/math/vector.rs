@@ -1,10 +1,13 @@
-use num::{Zero, Signed};
+use num::{Zero, Signed, NumCast};
+use std::f64;
 use math::point::{Point2, Point3};
 use math::normal::Normal3;
 use std::convert::From;
 use math::common::*;
 use math::scalar::*;
 use std::ops::*;
+use std::iter::Sum;
+use std::fmt;
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub struct Vector2<T> {
@@ -50,6 +53,92 @@ impl <T: BaseNum> Vector3<T> {
     pub fn permute(&self, x: Dimension3, y: Dimension3, z: Dimension3) -> Vector3<T> {
         Vector3::new(self[x], self[y], self[z])
     }
+
+    pub fn permute_indices(&self, idx: [usize; 3]) -> Vector3<T> {
+        Vector3::new(self[idx[0]], self[idx[1]], self[idx[2]])
+    }
+
+    pub fn as_array(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn to_homogeneous(self) -> [T; 4] {
+        [self.x, self.y, self.z, T::zero()]
+    }
+
+    pub fn cmplt(self, other: Vector3<T>) -> [bool; 3] {
+        [self.x < other.x, self.y < other.y, self.z < other.z]
+    }
+
+    pub fn cmpgt(self, other: Vector3<T>) -> [bool; 3] {
+        [self.x > other.x, self.y > other.y, self.z > other.z]
+    }
+}
+
+impl <T: BaseNum + Signed> Vector3<T> {
+    pub fn abs_dot(self, n: Normal3<T>) -> T {
+        abs_dot(self, n)
+    }
+}
+
+impl <T: BaseFloat> Vector3<T> {
+    pub fn has_nans(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    pub fn cmpeq(self, other: Vector3<T>, epsilon: T) -> [bool; 3] {
+        [self.x.approx_eq(other.x, epsilon), self.y.approx_eq(other.y, epsilon), self.z.approx_eq(other.z, epsilon)]
+    }
+
+    pub fn clamp(self, lo: T, hi: T) -> Vector3<T> {
+        Vector3::new(clamp(self.x, lo, hi), clamp(self.y, lo, hi), clamp(self.z, lo, hi))
+    }
+
+    pub fn saturate(self) -> Vector3<T> {
+        self.clamp(T::zero(), T::one())
+    }
+
+    pub fn floor_to_i32(self) -> Vector3<IntScalar> {
+        Vector3::new(saturating_to_int(self.x.floor()), saturating_to_int(self.y.floor()), saturating_to_int(self.z.floor()))
+    }
+
+    pub fn ceil_to_i32(self) -> Vector3<IntScalar> {
+        Vector3::new(saturating_to_int(self.x.ceil()), saturating_to_int(self.y.ceil()), saturating_to_int(self.z.ceil()))
+    }
+
+    pub fn round_to_i32(self) -> Vector3<IntScalar> {
+        Vector3::new(saturating_to_int(self.x.round()), saturating_to_int(self.y.round()), saturating_to_int(self.z.round()))
+    }
+
+    // Rodrigues' rotation formula, applied directly instead of building a rotation matrix.
+    pub fn rotate_around(self, axis: Vector3<T>, theta: T) -> Vector3<T> {
+        let k = axis.normalize();
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        self * cos_theta + k.cross(self) * sin_theta + k * (k.dot(self) * (T::one() - cos_theta))
+    }
+
+    // Projecting onto a zero vector yields the zero vector.
+    pub fn project_onto(self, onto: Vector3<T>) -> Vector3<T> {
+        let len_squared = onto.magnitude_squared();
+        if len_squared == T::zero() {
+            Vector3::zero()
+        } else {
+            onto * (self.dot(onto) / len_squared)
+        }
+    }
+
+    pub fn reject_from(self, onto: Vector3<T>) -> Vector3<T> {
+        self - self.project_onto(onto)
+    }
+
+    // The mean of an empty slice is the zero vector.
+    pub fn mean(vs: &[Vector3<T>]) -> Vector3<T> {
+        if vs.is_empty() {
+            Vector3::zero()
+        } else {
+            vs.iter().sum::<Vector3<T>>() / T::from(vs.len()).unwrap()
+        }
+    }
 }
 
 impl <T: BaseNum> From<T> for Vector3<T> {
@@ -70,6 +159,18 @@ impl <T: BaseNum> From<Normal3<T>> for Vector3<T> {
     }
 }
 
+impl <T: BaseNum> From<[T; 3]> for Vector3<T> {
+    fn from(a: [T; 3]) -> Vector3<T> {
+        Vector3::new(a[0], a[1], a[2])
+    }
+}
+
+impl <T: BaseNum> From<Vector3<T>> for [T; 3] {
+    fn from(v: Vector3<T>) -> [T; 3] {
+        v.as_array()
+    }
+}
+
 impl <T: BaseNum> Index<usize> for Vector3<T> {
     type Output = T;
 
@@ -95,6 +196,27 @@ impl <T: BaseNum> Index<Dimension3> for Vector3<T> {
     }
 }
 
+impl <T: BaseNum> IndexMut<usize> for Vector3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+impl <T: BaseNum> IndexMut<Dimension3> for Vector3<T> {
+    fn index_mut(&mut self, index: Dimension3) -> &mut T {
+        match index {
+            Dimension3::X => &mut self.x,
+            Dimension3::Y => &mut self.y,
+            Dimension3::Z => &mut self.z,
+        }
+    }
+}
+
 impl <T: BaseNum> Zero for Vector3<T> {
     fn zero() -> Vector3<T> {
         Vector3::new(T::zero(), T::zero(), T::zero())
@@ -105,6 +227,18 @@ impl <T: BaseNum> Zero for Vector3<T> {
     }
 }
 
+impl <T: BaseNum> Sum for Vector3<T> {
+    fn sum<I: Iterator<Item = Vector3<T>>>(iter: I) -> Vector3<T> {
+        iter.fold(Vector3::zero(), |acc, v| acc + v)
+    }
+}
+
+impl <'a, T: BaseNum> Sum<&'a Vector3<T>> for Vector3<T> {
+    fn sum<I: Iterator<Item = &'a Vector3<T>>>(iter: I) -> Vector3<T> {
+        iter.fold(Vector3::zero(), |acc, &v| acc + v)
+    }
+}
+
 impl <T: BaseNum + Neg<Output = T>> Neg for Vector3<T> {
     type Output = Vector3<T>;
 
@@ -177,6 +311,38 @@ impl <T: BaseNum> DivAssign<T> for Vector3<T> {
     }
 }
 
+impl <T: BaseNum> Mul for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+}
+
+impl <T: BaseNum> MulAssign for Vector3<T> {
+    fn mul_assign(&mut self, other: Vector3<T>) {
+        self.x *= other.x;
+        self.y *= other.y;
+        self.z *= other.z;
+    }
+}
+
+impl <T: BaseNum> Div for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn div(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::new(self.x / other.x, self.y / other.y, self.z / other.z)
+    }
+}
+
+impl <T: BaseNum> DivAssign for Vector3<T> {
+    fn div_assign(&mut self, other: Vector3<T>) {
+        self.x /= other.x;
+        self.y /= other.y;
+        self.z /= other.z;
+    }
+}
+
 impl <T: BaseNum> ComponentWise for Vector3<T> {
     type Scalar = T;
     type Dimension = Dimension3;
@@ -190,9 +356,9 @@ impl <T: BaseNum> ComponentWise for Vector3<T> {
     }
 
     fn max_dimension(self) -> Dimension3 {
-        if self.x > self.y && self.x > self.z {
+        if self.x >= self.y && self.x >= self.z {
             Dimension3::X
-        } else if self.y > self.x && self.y > self.z {
+        } else if self.y >= self.z {
             Dimension3::Y
         } else {
             Dimension3::Z
@@ -274,6 +440,31 @@ impl <T: BaseFloat> LinearInterpolate for Vector3<T> {
     type Scalar = T;
 }
 
+impl <T: BaseFloat> ApproxEq for Vector3<T> {
+    type Scalar = T;
+
+    fn approx_eq(self, other: Vector3<T>, epsilon: T) -> bool {
+        self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon) && self.z.approx_eq(other.z, epsilon)
+    }
+
+    fn relative_eq(self, other: Vector3<T>, epsilon: T) -> bool {
+        self.x.relative_eq(other.x, epsilon) && self.y.relative_eq(other.y, epsilon)
+            && self.z.relative_eq(other.z, epsilon)
+    }
+}
+
+impl <T: BaseNum + fmt::Display> fmt::Display for Vector3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component)
+    }
+}
+
+impl <T: BaseNum + fmt::LowerExp> fmt::LowerExp for Vector3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component_exp)
+    }
+}
+
 
 //
 // Vector2
@@ -297,6 +488,52 @@ impl <T: BaseNum> Vector2<T> {
     pub fn unit_y() -> Vector2<T> {
         Vector2::new(T::zero(), T::one())
     }
+
+    pub fn cross(self, other: Vector2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn as_array(&self) -> [T; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl <T: BaseNum + Signed> Vector2<T> {
+    // The vector rotated 90° counter-clockwise.
+    pub fn perp(self) -> Vector2<T> {
+        Vector2::new(-self.y, self.x)
+    }
+}
+
+impl <T: BaseFloat> Vector2<T> {
+    pub fn has_nans(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan()
+    }
+
+    pub fn clamp(self, lo: T, hi: T) -> Vector2<T> {
+        Vector2::new(clamp(self.x, lo, hi), clamp(self.y, lo, hi))
+    }
+
+    pub fn saturate(self) -> Vector2<T> {
+        self.clamp(T::zero(), T::one())
+    }
+
+    pub fn floor_to_i32(self) -> Vector2<IntScalar> {
+        Vector2::new(saturating_to_int(self.x.floor()), saturating_to_int(self.y.floor()))
+    }
+
+    pub fn ceil_to_i32(self) -> Vector2<IntScalar> {
+        Vector2::new(saturating_to_int(self.x.ceil()), saturating_to_int(self.y.ceil()))
+    }
+
+    pub fn round_to_i32(self) -> Vector2<IntScalar> {
+        Vector2::new(saturating_to_int(self.x.round()), saturating_to_int(self.y.round()))
+    }
+
+    pub fn rotate(self, theta: T) -> Vector2<T> {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        Vector2::new(self.x * cos_theta - self.y * sin_theta, self.x * sin_theta + self.y * cos_theta)
+    }
 }
 
 impl <T: BaseNum> From<T> for Vector2<T> {
@@ -311,6 +548,18 @@ impl <T: BaseNum> From<Point2<T>> for Vector2<T> {
     }
 }
 
+impl <T: BaseNum> From<[T; 2]> for Vector2<T> {
+    fn from(a: [T; 2]) -> Vector2<T> {
+        Vector2::new(a[0], a[1])
+    }
+}
+
+impl <T: BaseNum> From<Vector2<T>> for [T; 2] {
+    fn from(v: Vector2<T>) -> [T; 2] {
+        v.as_array()
+    }
+}
+
 impl <T: BaseNum> Index<usize> for Vector2<T> {
     type Output = T;
 
@@ -334,6 +583,25 @@ impl <T: BaseNum> Index<Dimension2> for Vector2<T> {
     }
 }
 
+impl <T: BaseNum> IndexMut<usize> for Vector2<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+impl <T: BaseNum> IndexMut<Dimension2> for Vector2<T> {
+    fn index_mut(&mut self, index: Dimension2) -> &mut T {
+        match index {
+            Dimension2::X => &mut self.x,
+            Dimension2::Y => &mut self.y,
+        }
+    }
+}
+
 impl <T: BaseNum> Zero for Vector2<T> {
     fn zero() -> Vector2<T> {
         Vector2::new(T::zero(), T::zero())
@@ -344,6 +612,18 @@ impl <T: BaseNum> Zero for Vector2<T> {
     }
 }
 
+impl <T: BaseNum> Sum for Vector2<T> {
+    fn sum<I: Iterator<Item = Vector2<T>>>(iter: I) -> Vector2<T> {
+        iter.fold(Vector2::zero(), |acc, v| acc + v)
+    }
+}
+
+impl <'a, T: BaseNum> Sum<&'a Vector2<T>> for Vector2<T> {
+    fn sum<I: Iterator<Item = &'a Vector2<T>>>(iter: I) -> Vector2<T> {
+        iter.fold(Vector2::zero(), |acc, &v| acc + v)
+    }
+}
+
 impl <T: BaseNum + Neg<Output = T>> Neg for Vector2<T> {
     type Output = Vector2<T>;
 
@@ -412,6 +692,36 @@ impl <T: BaseNum> DivAssign<T> for Vector2<T> {
     }
 }
 
+impl <T: BaseNum> Mul for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn mul(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.x * other.x, self.y * other.y)
+    }
+}
+
+impl <T: BaseNum> MulAssign for Vector2<T> {
+    fn mul_assign(&mut self, other: Vector2<T>) {
+        self.x *= other.x;
+        self.y *= other.y;
+    }
+}
+
+impl <T: BaseNum> Div for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn div(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.x / other.x, self.y / other.y)
+    }
+}
+
+impl <T: BaseNum> DivAssign for Vector2<T> {
+    fn div_assign(&mut self, other: Vector2<T>) {
+        self.x /= other.x;
+        self.y /= other.y;
+    }
+}
+
 impl <T: BaseNum> ComponentWise for Vector2<T> {
     type Scalar = T;
     type Dimension = Dimension2;
@@ -425,7 +735,7 @@ impl <T: BaseNum> ComponentWise for Vector2<T> {
     }
 
     fn max_dimension(self) -> Dimension2 {
-        if self.x > self.y {
+        if self.x >= self.y {
             Dimension2::X
         } else {
             Dimension2::Y
@@ -481,6 +791,30 @@ impl <T: BaseFloat> LinearInterpolate for Vector2<T> {
     type Scalar = T;
 }
 
+impl <T: BaseFloat> ApproxEq for Vector2<T> {
+    type Scalar = T;
+
+    fn approx_eq(self, other: Vector2<T>, epsilon: T) -> bool {
+        self.x.approx_eq(other.x, epsilon) && self.y.approx_eq(other.y, epsilon)
+    }
+
+    fn relative_eq(self, other: Vector2<T>, epsilon: T) -> bool {
+        self.x.relative_eq(other.x, epsilon) && self.y.relative_eq(other.y, epsilon)
+    }
+}
+
+impl <T: BaseNum + fmt::Display> fmt::Display for Vector2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component)
+    }
+}
+
+impl <T: BaseNum + fmt::LowerExp> fmt::LowerExp for Vector2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_components(f, &self.as_array(), format_component_exp)
+    }
+}
+
 pub fn vec2<T: BaseNum>(x: T, y: T) -> Vector2<T> {
     Vector2::new(x, y)
 }
@@ -489,16 +823,314 @@ pub fn vec3<T: BaseNum>(x: T, y: T, z: T) -> Vector3<T> {
     Vector3::new(x, y, z)
 }
 
+// Builds an orthonormal basis around the unit vector `v1` using the branchless construction from
+// Duff et al., "Building an Orthonormal Basis, Revisited", which stays stable near the z axis.
 pub fn coordinate_system<T: BaseFloat>(v1: Vector3<T>) -> (Vector3<T>, Vector3<T>) {
-    let v2 = if v1.x.abs() > v1.y.abs() {
-        Vector3::new(-v1.z, T::zero(), v1.x).normalize()
+    debug_assert!(v1.is_normalized(T::from(1e-4).unwrap()), "coordinate_system expects a normalized vector");
+
+    let sign = if v1.z.is_sign_negative() { -T::one() } else { T::one() };
+    let a = -T::one() / (sign + v1.z);
+    let b = v1.x * v1.y * a;
+    (Vector3::new(T::one() + sign * v1.x * v1.x * a, sign * b, -sign * v1.x),
+     Vector3::new(b, sign + v1.y * v1.y * a, -v1.y))
+}
+
+// Returns `v` with its component along `reference` removed, normalized.
+pub fn gram_schmidt<T: BaseFloat>(v: Vector3<T>, reference: Vector3<T>) -> Vector3<T> {
+    v.reject_from(reference).normalize()
+}
+
+// Orthonormalizes three vectors in order, so the first keeps its direction and each later one is
+// made orthogonal to those before it.
+pub fn gram_schmidt_basis<T: BaseFloat>(v0: Vector3<T>, v1: Vector3<T>, v2: Vector3<T>)
+        -> (Vector3<T>, Vector3<T>, Vector3<T>) {
+    let e0 = v0.normalize();
+    let e1 = gram_schmidt(v1, e0);
+    let e2 = gram_schmidt(v2.reject_from(e0), e1);
+    (e0, e1, e2)
+}
+
+// `incident` points towards the surface, and the result points away from it on the same side.
+pub fn reflect<T: BaseNum>(incident: Vector3<T>, n: Vector3<T>) -> Vector3<T> {
+    incident - n * ((T::one() + T::one()) * incident.dot(n))
+}
+
+// Uses the same convention as `reflect`: `incident` points towards the surface, on the side `n`
+// faces, and the result continues through to the other side. `eta` is the ratio of the indices of
+// refraction, incident over transmitted. Returns `None` on total internal reflection.
+pub fn refract<T: BaseFloat>(incident: Vector3<T>, n: Vector3<T>, eta: T) -> Option<Vector3<T>> {
+    refract_with_cos(incident, n, eta, -incident.dot(n))
+}
+
+// `refract` with a precomputed `cos_theta_i`, the cosine between `n` and the reversed incident
+// direction (`-incident · n`).
+pub fn refract_with_cos<T: BaseFloat>(incident: Vector3<T>, n: Vector3<T>, eta: T, cos_theta_i: T)
+        -> Option<Vector3<T>> {
+    let sin2_theta_i = partial_max(T::zero(), T::one() - cos_theta_i * cos_theta_i);
+    let sin2_theta_t = eta * eta * sin2_theta_i;
+
+    if sin2_theta_t >= T::one() {
+        None
     } else {
-        Vector3::new(T::zero(), v1.z, -v1.y).normalize()
-    };
-    (v2, v1.cross(v2))
+        let cos_theta_t = (T::one() - sin2_theta_t).sqrt();
+        Some(incident * eta + n * (eta * cos_theta_i - cos_theta_t))
+    }
+}
+
+pub fn spherical_direction<T: BaseFloat>(sin_theta: T, cos_theta: T, phi: T) -> Vector3<T> {
+    Vector3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta)
+}
+
+pub fn spherical_theta<T: BaseFloat>(v: Vector3<T>) -> T {
+    clamp(v.z, -T::one(), T::one()).acos()
+}
+
+pub fn spherical_phi<T: BaseFloat>(v: Vector3<T>) -> T {
+    let phi = v.y.atan2(v.x);
+    if phi < T::zero() {
+        phi + <T as NumCast>::from(2.0 * f64::consts::PI).unwrap()
+    } else {
+        phi
+    }
 }
 
 pub type Vector3i = Vector3<IntScalar>;
 pub type Vector3f = Vector3<FloatScalar>;
 pub type Vector2i = Vector2<IntScalar>;
-pub type Vector2f = Vector2<FloatScalar>;
\ No newline at end of file
+pub type Vector2f = Vector2<FloatScalar>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_as_array() {
+        let v = vec3(1.0, 2.5, -3.0);
+        assert_eq!(format!("{}", v), "[1, 2.5, -3]");
+        assert_eq!(format!("{:.3}", v), "[1.000, 2.500, -3.000]");
+        assert_eq!(format!("{:e}", vec3(1500.0, 0.25, -2.0)), "[1.5e3, 2.5e-1, -2e0]");
+        assert_eq!(format!("{:.1e}", vec2(1500, -2)), "[1.5e3, -2.0e0]");
+    }
+
+    #[test]
+    fn refract_with_cos_matches_refract() {
+        let n = Vector3f::unit_y();
+        let incident = vec3(1.0, -2.0, 0.0).normalize();
+        let cos_theta_i = -incident.dot(n);
+        let t = refract(incident, n, 1.0 / 1.5).unwrap();
+        assert_eq!(refract_with_cos(incident, n, 1.0 / 1.5, cos_theta_i), Some(t));
+        assert!(t.is_normalized(1e-5));
+
+        // Leaving glass at a shallow angle is totally internally reflected.
+        let grazing = vec3(1.0, -0.2, 0.0).normalize();
+        assert_eq!(refract(grazing, n, 1.5), None);
+        assert_eq!(refract_with_cos(grazing, n, 1.5, -grazing.dot(n)), None);
+    }
+
+    #[test]
+    fn rotate_vector2() {
+        assert!(Vector2f::unit_x().rotate(radians(90.0)).approx_eq(Vector2f::unit_y(), 1e-6));
+        assert!(vec2(1.0, 1.0).rotate(radians(180.0)).approx_eq(vec2(-1.0, -1.0), 1e-6));
+    }
+
+    #[test]
+    fn reflect_and_refract_share_a_convention() {
+        let n = Vector3f::unit_y();
+        assert_eq!(reflect(vec3(1.0, -1.0, 0.0), n), vec3(1.0, 1.0, 0.0));
+
+        let incident = vec3(1.0, -1.0, 0.0).normalize();
+        assert!(refract(incident, n, 1.0).unwrap().approx_eq(incident, 1e-6));
+        assert_eq!(refract(vec3(1.0, -0.01, 0.0).normalize(), n, 1.2), None);
+    }
+
+    #[test]
+    fn cross_vector2() {
+        assert_eq!(Vector2f::unit_x().cross(Vector2f::unit_y()), 1.0);
+        assert_eq!(Vector2f::unit_y().cross(Vector2f::unit_x()), -1.0);
+        assert_eq!(vec2(2, 3).cross(vec2(4, 6)), 0);
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut v = Vector3i::zero();
+        v[0] = 1;
+        v[1usize] += 2;
+        v[Dimension3::Z] = 3;
+        assert_eq!(v, vec3(1, 2, 3));
+        v[Dimension3::X] = 4;
+        v[Dimension3::Y] *= 5;
+        v[2] -= 1;
+        assert_eq!(v, vec3(4, 10, 2));
+
+        let mut v = Vector2i::zero();
+        v[0] = 1;
+        v[Dimension2::Y] = 2;
+        assert_eq!(v, vec2(1, 2));
+        v[Dimension2::X] = 3;
+        v[1] = 4;
+        assert_eq!(v, vec2(3, 4));
+    }
+
+    #[test]
+    fn has_nans() {
+        assert!(Vector3f::new(FloatScalar::NAN, 0.0, 0.0).has_nans());
+        assert!(!Vector3f::new(1.0, FloatScalar::INFINITY, 0.0).has_nans());
+        assert!(Vector2f::new(0.0, FloatScalar::NAN).has_nans());
+        assert!(!Vector2f::new(0.0, 1.0).has_nans());
+    }
+
+    #[test]
+    fn component_wise_mul_and_div() {
+        assert_eq!(vec3(2, 3, 4) * vec3(1, 0, 2), vec3(2, 0, 8));
+        assert_eq!(vec3(2.0, 3.0, 4.0) / vec3(4.0, 1.0, 2.0), vec3(0.5, 3.0, 2.0));
+        assert_eq!(vec2(2, 3) * vec2(5, -1), vec2(10, -3));
+        assert_eq!(vec2(2.0, 3.0) / vec2(4.0, 2.0), vec2(0.5, 1.5));
+
+        let mut v = vec3(2, 3, 4);
+        v *= vec3(1, 0, 2);
+        assert_eq!(v, vec3(2, 0, 8));
+        v /= vec3(2, 1, 4);
+        assert_eq!(v, vec3(1, 0, 2));
+
+        let mut v = vec2(2.0, 3.0);
+        v *= vec2(2.0, 0.5);
+        assert_eq!(v, vec2(4.0, 1.5));
+        v /= vec2(4.0, 1.5);
+        assert_eq!(v, vec2(1.0, 1.0));
+    }
+
+    #[test]
+    fn spherical_coordinates() {
+        assert!(spherical_direction(1.0, 0.0, 0.0).approx_eq(Vector3f::unit_x(), 1e-6));
+        assert!(spherical_direction(1.0, 0.0, radians(90.0)).approx_eq(Vector3f::unit_y(), 1e-6));
+        assert_eq!(spherical_theta(Vector3f::unit_z()), 0.0);
+        assert!(spherical_theta(-Vector3f::unit_x()).approx_eq(radians(90.0), 1e-6));
+        assert!(spherical_phi(-Vector3f::unit_y()).approx_eq(radians(270.0), 1e-5));
+    }
+
+    #[test]
+    fn rotate_around_axis() {
+        let z = Vector3f::unit_z();
+        assert!(Vector3f::unit_x().rotate_around(z, radians(90.0)).approx_eq(Vector3f::unit_y(), 1e-6));
+        assert!(Vector3f::unit_x().rotate_around(z * 3.0, radians(90.0)).approx_eq(Vector3f::unit_y(), 1e-6));
+
+        let v = vec3(1.0, -2.0, 0.5);
+        assert!(v.rotate_around(vec3(1.0, 1.0, 1.0), radians(360.0)).approx_eq(v, 1e-5));
+    }
+
+    #[test]
+    fn max_dimension_ties_pick_the_first_axis() {
+        assert_eq!(vec3(1, 1, 0).max_dimension(), Dimension3::X);
+        assert_eq!(vec3(0, 0, 0).max_dimension(), Dimension3::X);
+        assert_eq!(vec3(5, 5, 5).max_dimension(), Dimension3::X);
+        assert_eq!(vec3(0, 2, 2).max_dimension(), Dimension3::Y);
+        assert_eq!(vec3(0, 1, 2).max_dimension(), Dimension3::Z);
+        assert_eq!(vec2(3, 3).max_dimension(), Dimension2::X);
+        assert_eq!(vec2(1, 3).max_dimension(), Dimension2::Y);
+    }
+
+    #[test]
+    fn coordinate_system_is_orthonormal() {
+        let inputs = [Vector3f::unit_x(), Vector3f::unit_y(), Vector3f::unit_z(), -Vector3f::unit_z(),
+                      vec3(1e-4, 0.0, 1.0).normalize(), vec3(0.0, 1e-4, -1.0).normalize(),
+                      vec3(1.0, 2.0, 3.0).normalize(), vec3(-3.0, 0.5, -1.0).normalize()];
+        for &v1 in inputs.iter() {
+            let (v2, v3) = coordinate_system(v1);
+            assert!(v2.is_normalized(1e-5) && v3.is_normalized(1e-5), "not unit length for {}", v1);
+            assert!(v1.dot(v2).abs() < 1e-6 && v1.dot(v3).abs() < 1e-6 && v2.dot(v3).abs() < 1e-6,
+                    "not orthogonal for {}", v1);
+            assert!(v1.cross(v2).approx_eq(v3, 1e-5), "not right-handed for {}", v1);
+        }
+    }
+
+    #[test]
+    fn project_and_reject() {
+        let v = vec3(1.0, 1.0, 0.0);
+        assert_eq!(v.project_onto(Vector3f::unit_x()), vec3(1.0, 0.0, 0.0));
+        assert_eq!(v.reject_from(Vector3f::unit_x()), vec3(0.0, 1.0, 0.0));
+        assert_eq!(v.project_onto(Vector3f::unit_x() * 4.0), vec3(1.0, 0.0, 0.0));
+
+        assert_eq!(v.project_onto(Vector3f::zero()), Vector3f::zero());
+        assert_eq!(v.reject_from(Vector3f::zero()), v);
+    }
+
+    #[test]
+    fn sum_and_mean() {
+        let vs = [vec3(1.0, 2.0, 3.0), vec3(4.0, 5.0, 6.0), vec3(-2.0, 2.0, 0.0)];
+        assert_eq!(vs.iter().sum::<Vector3f>(), vec3(3.0, 9.0, 9.0));
+        assert_eq!(vs.iter().cloned().sum::<Vector3f>(), vec3(3.0, 9.0, 9.0));
+        assert_eq!(Vector3::mean(&vs), vec3(1.0, 3.0, 3.0));
+        assert_eq!(Vector3f::mean(&[]), Vector3f::zero());
+        assert_eq!([vec2(1, 2), vec2(3, 4)].iter().sum::<Vector2i>(), vec2(4, 6));
+    }
+
+    #[test]
+    fn gram_schmidt_orthonormalizes() {
+        let v = gram_schmidt(vec3(1.0, 1.0, 0.0), Vector3f::unit_x());
+        assert!(v.approx_eq(Vector3f::unit_y(), 1e-6));
+
+        let (e0, e1, e2) = gram_schmidt_basis(vec3(2.0, 0.0, 0.0), vec3(1.0, 3.0, 0.0), vec3(1.0, 1.0, 5.0));
+        assert_eq!(e0, Vector3f::unit_x());
+        for &(a, b) in [(e0, e1), (e0, e2), (e1, e2)].iter() {
+            assert!(a.dot(b).abs() < 1e-6);
+        }
+        assert!(e1.is_normalized(1e-6) && e2.is_normalized(1e-6));
+
+        let (_, e1, e2) = gram_schmidt_basis(vec3(1.0, 2.0, 3.0), vec3(-1.0, 0.5, 2.0), vec3(0.0, 1.0, -1.0));
+        assert!(e1.dot(e2).abs() < 1e-5 && e1.is_normalized(1e-5) && e2.is_normalized(1e-5));
+    }
+
+    #[test]
+    fn clamp_and_saturate() {
+        assert_eq!(vec3(-0.5, 0.5, 2.0).clamp(0.0, 1.0), vec3(0.0, 0.5, 1.0));
+        assert_eq!(vec3(-0.5, 0.5, 2.0).saturate(), vec3(0.0, 0.5, 1.0));
+        assert_eq!(vec2(-3.0, 3.0).clamp(-1.0, 2.0), vec2(-1.0, 2.0));
+    }
+
+    #[test]
+    fn permute_indices() {
+        assert_eq!(vec3(1, 2, 3).permute_indices([2, 0, 1]), vec3(3, 1, 2));
+        assert_eq!(vec3(1, 2, 3).permute(Dimension3::Z, Dimension3::X, Dimension3::Y), vec3(3, 1, 2));
+    }
+
+    #[test]
+    fn perp() {
+        assert_eq!(Vector2f::unit_x().perp(), Vector2f::unit_y());
+        assert_eq!(vec2(2, 3).perp(), vec2(-3, 2));
+        assert_eq!(vec2(2, 3).perp().dot(vec2(2, 3)), 0);
+    }
+
+    #[test]
+    fn float_to_int_conversions() {
+        let v = vec3(1.9, -0.1, 2.0);
+        assert_eq!(v.floor_to_i32(), vec3(1, -1, 2));
+        assert_eq!(v.ceil_to_i32(), vec3(2, 0, 2));
+        assert_eq!(v.round_to_i32(), vec3(2, 0, 2));
+
+        let v = vec3(1e20, -1e20, FloatScalar::NAN);
+        assert_eq!(v.floor_to_i32(), vec3(IntScalar::MAX, IntScalar::MIN, 0));
+        assert_eq!(vec2(FloatScalar::INFINITY, -2.5).round_to_i32(), vec2(IntScalar::MAX, -3));
+    }
+
+    #[test]
+    fn component_comparisons() {
+        let v = vec3(1.0, 5.0, 3.0);
+        let two = Vector3f::from(2.0);
+        assert_eq!(v.cmplt(two), [true, false, false]);
+        assert_eq!(v.cmpgt(two), [false, true, true]);
+        assert_eq!(v.cmpeq(vec3(1.0, 5.000001, 2.0), 1e-5), [true, true, false]);
+
+        assert!(all(&v.cmpgt(Vector3f::zero())));
+        assert!(!all(&v.cmplt(two)));
+        assert!(any(&v.cmplt(two)));
+        assert!(!any(&v.cmplt(Vector3f::zero())));
+    }
+
+    #[test]
+    fn array_round_trip() {
+        let v = Vector3f::from([1.0, 2.0, 3.0]);
+        assert_eq!(v, vec3(1.0, 2.0, 3.0));
+        assert_eq!(<[FloatScalar; 3]>::from(v), [1.0, 2.0, 3.0]);
+        assert_eq!(<[IntScalar; 2]>::from(Vector2i::from([4, 5])), [4, 5]);
+    }
+}
@@ -0,0 +1,78 @@
+use math::common::*;
+use math::point::Point3f;
+use math::normal::Normal3f;
+use math::ray::Ray;
+use math::scalar::*;
+
+#[derive(PartialEq, Copy, Clone)]
+pub struct Sphere {
+    pub center: Point3f,
+    pub radius: FloatScalar,
+}
+
+impl Sphere {
+    pub fn new(center: Point3f, radius: FloatScalar) -> Sphere {
+        Sphere {
+            center: center,
+            radius: radius,
+        }
+    }
+
+    // Returns the nearest hit in front of the ray's origin that is no further than `ray.tmax`.
+    pub fn intersect(&self, ray: &Ray) -> Option<FloatScalar> {
+        let oc = ray.origin - self.center;
+        let a = ray.direction.magnitude_squared();
+        let b = 2.0 * dot(oc, ray.direction);
+        let c = oc.magnitude_squared() - self.radius * self.radius;
+
+        let (t0, t1) = solve_quadratic(a, b, c)?;
+        if t0 > ray.tmax || t1 <= 0.0 {
+            return None;
+        }
+
+        let t = if t0 > 0.0 { t0 } else { t1 };
+        if t > ray.tmax {
+            None
+        } else {
+            Some(t)
+        }
+    }
+
+    pub fn normal_at(&self, p: Point3f) -> Normal3f {
+        Normal3f::from(p - self.center).normalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::vector::vec3;
+
+    fn unit_sphere() -> Sphere {
+        Sphere::new(Point3f::new(0.0, 0.0, 0.0), 1.0)
+    }
+
+    #[test]
+    fn direct_hit() {
+        let ray = Ray::new(Point3f::new(0.0, 0.0, -5.0), vec3(0.0, 0.0, 1.0));
+        assert_eq!(unit_sphere().intersect(&ray), Some(4.0));
+        assert_eq!(unit_sphere().intersect(&ray.with_tmax(3.0)), None);
+        // From inside, the far side is the hit.
+        let inside = Ray::new(Point3f::new(0.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0));
+        assert_eq!(unit_sphere().intersect(&inside), Some(1.0));
+    }
+
+    #[test]
+    fn tangent_hit() {
+        let ray = Ray::new(Point3f::new(1.0, 0.0, -5.0), vec3(0.0, 0.0, 1.0));
+        assert_eq!(unit_sphere().intersect(&ray), Some(5.0));
+    }
+
+    #[test]
+    fn misses() {
+        let beside = Ray::new(Point3f::new(1.5, 0.0, -5.0), vec3(0.0, 0.0, 1.0));
+        assert_eq!(unit_sphere().intersect(&beside), None);
+        let behind = Ray::new(Point3f::new(0.0, 0.0, 5.0), vec3(0.0, 0.0, 1.0));
+        assert_eq!(unit_sphere().intersect(&behind), None);
+    }
+}
@@ -0,0 +1,122 @@
+use num::Float;
+use math::common::*;
+use math::point::Point3f;
+use math::normal::Normal3f;
+use math::ray::Ray;
+use math::scalar::*;
+
+// Möller–Trumbore ray/triangle intersection. On a hit, returns the ray parameter `t` and the
+// barycentric coordinates `(b1, b2)` of the hit with respect to `p1` and `p2`.
+pub fn intersect_triangle(ray: &Ray, p0: Point3f, p1: Point3f, p2: Point3f)
+        -> Option<(FloatScalar, FloatScalar, FloatScalar)> {
+    let e1 = p1 - p0;
+    let e2 = p2 - p0;
+    let p = cross(ray.direction, e2);
+    let det = dot(e1, p);
+    // `det` scales with the edge and direction lengths, so the parallel cutoff has to as well or
+    // small triangles would be rejected outright.
+    let scale = e1.magnitude() * e2.magnitude() * ray.direction.magnitude();
+    if det.abs() <= FloatScalar::epsilon() * scale {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+    let s = ray.origin - p0;
+    let b1 = dot(s, p) * inv_det;
+    if !(0.0..=1.0).contains(&b1) {
+        return None;
+    }
+
+    let q = cross(s, e1);
+    let b2 = dot(ray.direction, q) * inv_det;
+    if b2 < 0.0 || b1 + b2 > 1.0 {
+        return None;
+    }
+
+    let t = dot(e2, q) * inv_det;
+    if t <= 0.0 || t > ray.tmax {
+        None
+    } else {
+        Some((t, b1, b2))
+    }
+}
+
+// The unit normal following the winding `p0 -> p1 -> p2`, or `None` if the triangle has no area.
+pub fn triangle_normal(p0: Point3f, p1: Point3f, p2: Point3f) -> Option<Normal3f> {
+    cross(p1 - p0, p2 - p0).try_normalize().map(Normal3f::from)
+}
+
+pub fn triangle_area(p0: Point3f, p1: Point3f, p2: Point3f) -> FloatScalar {
+    0.5 * cross(p1 - p0, p2 - p0).magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::vector::{Vector3f, vec3};
+
+    fn right_triangle(leg: FloatScalar) -> (Point3f, Point3f, Point3f) {
+        (Point3f::new(0.0, 0.0, 0.0), Point3f::new(leg, 0.0, 0.0), Point3f::new(0.0, leg, 0.0))
+    }
+
+    fn down_z(x: FloatScalar, y: FloatScalar, direction: Vector3f) -> Ray {
+        Ray::new(Point3f::new(x, y, 1.0), direction)
+    }
+
+    #[test]
+    fn hits_centroid() {
+        let (p0, p1, p2) = right_triangle(3.0);
+        let (t, b1, b2) = intersect_triangle(&down_z(1.0, 1.0, vec3(0.0, 0.0, -1.0)), p0, p1, p2).unwrap();
+        assert!(t.approx_eq(1.0, 1e-6));
+        assert!(b1.approx_eq(1.0 / 3.0, 1e-6));
+        assert!(b2.approx_eq(1.0 / 3.0, 1e-6));
+    }
+
+    #[test]
+    fn hits_vertex() {
+        let (p0, p1, p2) = right_triangle(3.0);
+        let (t, b1, b2) = intersect_triangle(&down_z(3.0, 0.0, vec3(0.0, 0.0, -1.0)), p0, p1, p2).unwrap();
+        assert_eq!((t, b1, b2), (1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn misses_beside_triangle() {
+        let (p0, p1, p2) = right_triangle(3.0);
+        assert!(intersect_triangle(&down_z(2.0, 2.0, vec3(0.0, 0.0, -1.0)), p0, p1, p2).is_none());
+    }
+
+    #[test]
+    fn misses_parallel_ray() {
+        let (p0, p1, p2) = right_triangle(3.0);
+        assert!(intersect_triangle(&down_z(1.0, 1.0, vec3(1.0, 0.0, 0.0)), p0, p1, p2).is_none());
+    }
+
+    #[test]
+    fn hits_small_triangles() {
+        for &leg in &[1e-2, 1e-3, 3e-4, 1e-4] {
+            let (p0, p1, p2) = right_triangle(leg);
+            let ray = down_z(leg / 3.0, leg / 3.0, vec3(0.0, 0.0, -1.0));
+            assert!(intersect_triangle(&ray, p0, p1, p2).is_some(), "missed triangle with legs {}", leg);
+        }
+
+        let (p0, p1, p2) = right_triangle(1e-2);
+        let ray = down_z(1e-2 / 3.0, 1e-2 / 3.0, vec3(0.0, 0.0, -1e-3));
+        let (t, _, _) = intersect_triangle(&ray, p0, p1, p2).unwrap();
+        assert!(t.approx_eq(1e3, 1e-2));
+    }
+
+    #[test]
+    fn area_and_normal() {
+        let (p0, p1, p2) = (Point3f::new(0.0, 0.0, 0.0), Point3f::new(3.0, 0.0, 0.0), Point3f::new(0.0, 4.0, 0.0));
+        assert_eq!(triangle_area(p0, p1, p2), 6.0);
+        assert!(triangle_normal(p0, p1, p2).unwrap().approx_eq(Normal3f::new(0.0, 0.0, 1.0), 1e-6));
+        assert!(triangle_normal(p0, p2, p1).unwrap().approx_eq(Normal3f::new(0.0, 0.0, -1.0), 1e-6));
+    }
+
+    #[test]
+    fn degenerate_triangle_has_no_normal() {
+        let (p0, p1, p2) = (Point3f::new(0.0, 0.0, 0.0), Point3f::new(1.0, 1.0, 1.0), Point3f::new(2.0, 2.0, 2.0));
+        assert_eq!(triangle_area(p0, p1, p2), 0.0);
+        assert!(triangle_normal(p0, p1, p2).is_none());
+    }
+}